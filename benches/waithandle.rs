@@ -1,4 +1,4 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use std::sync::mpsc::channel;
 use std::thread;
 use std::time::Duration;
@@ -21,9 +21,8 @@ fn channels() {
     let (tx, rx) = channel::<Message>();
     let thread = thread::spawn({
         move || loop {
-            match rx.recv_timeout(Duration::from_secs(1)) {
-                Ok(_) => break,
-                Err(_) => {}
+            if rx.recv_timeout(Duration::from_secs(1)).is_ok() {
+                break;
             }
         }
     });
@@ -32,10 +31,88 @@ fn channels() {
     thread.join().unwrap();
 }
 
+const ROUNDS: usize = 1000;
+
+fn waithandle_ping_pong() {
+    let (ping, ping_listener) = waithandle::new_auto_reset();
+    let (pong, pong_listener) = waithandle::new_auto_reset();
+    let thread = thread::spawn(move || {
+        for _ in 0..ROUNDS {
+            ping_listener.wait_forever();
+            pong.signal();
+        }
+    });
+    for _ in 0..ROUNDS {
+        ping.signal();
+        pong_listener.wait_forever();
+    }
+    thread.join().unwrap();
+}
+
+fn channels_ping_pong() {
+    let (ping, ping_rx) = channel::<()>();
+    let (pong, pong_rx) = channel::<()>();
+    let thread = thread::spawn(move || {
+        for _ in 0..ROUNDS {
+            ping_rx.recv().unwrap();
+            pong.send(()).unwrap();
+        }
+    });
+    for _ in 0..ROUNDS {
+        ping.send(()).unwrap();
+        pong_rx.recv().unwrap();
+    }
+    thread.join().unwrap();
+}
+
+fn check(signaled: bool) -> impl FnMut() -> usize {
+    let (signaler, listener) = waithandle::new();
+    if signaled {
+        signaler.signal();
+    }
+    move || {
+        // Keep the signaler alive, so the listener doesn't disconnect.
+        let _ = &signaler;
+        (0..1000).filter(|_| black_box(&listener).check()).count()
+    }
+}
+
+fn check_contended() -> usize {
+    let (signaler, listener) = waithandle::new();
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let listener = listener.clone();
+            thread::spawn(move || (0..10_000).filter(|_| listener.check()).count())
+        })
+        .collect();
+    signaler.signal();
+    threads.into_iter().map(|thread| thread.join().unwrap()).sum()
+}
+
+// Run the benchmarks with and without the `futex` feature
+// to compare the wait handle backends with each other.
+#[cfg(all(feature = "futex", target_os = "linux"))]
+const BACKEND: &str = "Futex";
+#[cfg(not(all(feature = "futex", target_os = "linux")))]
+const BACKEND: &str = "Condvar";
+
 fn criterion_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("Benchmarks");
-    group.bench_function("Waithandle", |b| b.iter(|| waithandle()));
-    group.bench_function("Channels", |b| b.iter(|| channels()));
+    group.bench_function(format!("Waithandle ({})", BACKEND), |b| b.iter(waithandle));
+    group.bench_function("Channels", |b| b.iter(channels));
+    group.finish();
+
+    let mut group = c.benchmark_group("Ping pong");
+    group.bench_function(format!("Waithandle ({})", BACKEND), |b| {
+        b.iter(waithandle_ping_pong)
+    });
+    group.bench_function("Channels", |b| b.iter(channels_ping_pong));
+    group.finish();
+
+    let mut group = c.benchmark_group("Check");
+    group.bench_function("Unsignaled", |b| b.iter(check(false)));
+    group.bench_function("Signaled", |b| b.iter(check(true)));
+    group.bench_function("Contended", |b| b.iter(check_contended));
     group.finish();
 }
 
@@ -0,0 +1,190 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::condition::Condition;
+use crate::{Operation, WaitHandleError, WaitHandleResult};
+
+///////////////////////////////////////////////////////////
+// Barrier state
+
+#[derive(Debug)]
+struct State {
+    arrived: usize,
+    generation: u64,
+    cancelled: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct BarrierState {
+    state: Mutex<State>,
+    condition: Condition,
+    participants: usize,
+}
+
+impl BarrierState {
+    pub fn new(participants: usize) -> Self {
+        BarrierState {
+            state: Mutex::new(State {
+                arrived: 0,
+                generation: 0,
+                cancelled: false,
+            }),
+            condition: Condition::new(),
+            participants,
+        }
+    }
+
+    pub fn wait(&self, timeout: Duration) -> WaitHandleResult<BarrierOutcome> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut guard = self.lock(Operation::Wait)?;
+        if guard.cancelled {
+            return Ok(BarrierOutcome::Cancelled);
+        }
+
+        guard.arrived += 1;
+        if guard.arrived >= self.participants {
+            // Everyone is here, so release the other participants
+            // and start over with the next generation.
+            guard.arrived = 0;
+            guard.generation = guard.generation.wrapping_add(1);
+            drop(guard);
+            self.condition.notify_all();
+            return Ok(BarrierOutcome::Leader);
+        }
+
+        let generation = guard.generation;
+        let mut guard = self
+            .condition
+            .wait_while(&self.state, guard, deadline, |state| {
+                state.generation == generation && !state.cancelled
+            })
+            .map_err(|err| WaitHandleError::poisoned(Operation::Wait, err))?;
+
+        if guard.generation != generation {
+            return Ok(BarrierOutcome::Follower);
+        }
+
+        // We're leaving the barrier without being released,
+        // so the remaining participants shouldn't count us.
+        guard.arrived -= 1;
+        if guard.cancelled {
+            return Ok(BarrierOutcome::Cancelled);
+        }
+        Ok(BarrierOutcome::TimedOut)
+    }
+
+    pub fn cancel(&self) -> WaitHandleResult<()> {
+        let mut guard = self.lock(Operation::Signal)?;
+        if !guard.cancelled {
+            guard.cancelled = true;
+            drop(guard);
+            self.condition.notify_all();
+        }
+        Ok(())
+    }
+
+    pub fn is_cancelled(&self) -> WaitHandleResult<bool> {
+        Ok(self.lock(Operation::Check)?.cancelled)
+    }
+
+    fn lock(&self, operation: Operation) -> WaitHandleResult<MutexGuard<'_, State>> {
+        self.state
+            .lock()
+            .map_err(|err| WaitHandleError::poisoned(operation, err))
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Outcome
+
+/// Describes how a barrier wait ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarrierOutcome {
+    /// The participant was the last one to arrive, and released the others.
+    Leader,
+    /// The participant was released by the last one to arrive.
+    Follower,
+    /// The timeout occured before every participant had arrived.
+    TimedOut,
+    /// The barrier was cancelled.
+    Cancelled,
+}
+
+impl BarrierOutcome {
+    /// Checks whether or not the participant was released by the barrier.
+    pub fn is_released(&self) -> bool {
+        matches!(self, BarrierOutcome::Leader | BarrierOutcome::Follower)
+    }
+
+    /// Checks whether or not the participant was the last one to arrive.
+    pub fn is_leader(&self) -> bool {
+        matches!(self, BarrierOutcome::Leader)
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Canceller
+
+/// The cancelling half of a barrier.
+///
+/// Cancelling the barrier releases every participant waiting for it,
+/// and makes every subsequent wait return right away.
+#[derive(Debug, Clone)]
+pub struct BarrierCanceller {
+    barrier: Arc<BarrierState>,
+}
+
+impl BarrierCanceller {
+    pub(crate) fn new(barrier: Arc<BarrierState>) -> Self {
+        Self { barrier }
+    }
+
+    /// Cancels the barrier
+    pub fn cancel(&self) {
+        self.try_cancel().expect("error occured while cancelling barrier")
+    }
+
+    /// Tries to cancel the barrier
+    pub fn try_cancel(&self) -> WaitHandleResult<()> {
+        self.barrier.cancel()
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Barrier
+
+/// The waiting half of a barrier.
+///
+/// Clone the barrier to hand it out to each participant.
+#[derive(Debug, Clone)]
+pub struct Barrier {
+    barrier: Arc<BarrierState>,
+}
+
+impl Barrier {
+    pub(crate) fn new(barrier: Arc<BarrierState>) -> Self {
+        Self { barrier }
+    }
+
+    /// Checks whether or not the barrier have been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.try_is_cancelled().expect("an error occured while checking barrier")
+    }
+
+    /// Tries checking whether or not the barrier have been cancelled.
+    pub fn try_is_cancelled(&self) -> WaitHandleResult<bool> {
+        self.barrier.is_cancelled()
+    }
+
+    /// Waits until every participant have arrived at the barrier,
+    /// the timeout occur or the barrier is cancelled, whichever comes first.
+    pub fn wait(&self, timeout: Duration) -> BarrierOutcome {
+        self.try_wait(timeout).expect("an error occured while waiting for barrier")
+    }
+
+    /// Tries waiting until every participant have arrived at the barrier,
+    /// the timeout occur or the barrier is cancelled, whichever comes first.
+    pub fn try_wait(&self, timeout: Duration) -> WaitHandleResult<BarrierOutcome> {
+        self.barrier.wait(timeout)
+    }
+}
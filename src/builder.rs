@@ -0,0 +1,79 @@
+use crate::{
+    PoisonPolicy, ResetMode, WaitHandle, WaitHandleListener, WaitHandleSignaler, WakeMode,
+};
+
+/// Configures and creates a wait handle pair for signaling and listening.
+///
+/// ```rust
+/// use waithandle::{ResetMode, WaitHandleBuilder};
+///
+/// let (_signaler, listener) = WaitHandleBuilder::new()
+///     .name("shutdown")
+///     .reset_mode(ResetMode::Auto)
+///     .signaled(true)
+///     .build();
+///
+/// assert!(listener.check());  // Consumes the signal
+/// assert!(!listener.check()); // No longer signaled
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WaitHandleBuilder {
+    signaled: bool,
+    mode: ResetMode,
+    wake: Option<WakeMode>,
+    poison: PoisonPolicy,
+    name: Option<String>,
+}
+
+impl WaitHandleBuilder {
+    /// Creates a builder for an unsignaled, manual-reset wait handle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether or not the wait handle starts out signaled.
+    pub fn signaled(mut self, signaled: bool) -> Self {
+        self.signaled = signaled;
+        self
+    }
+
+    /// Sets whether or not the wait handle stays signaled after a successful wait.
+    pub fn reset_mode(mut self, mode: ResetMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets how many blocked threads a signal wakes up.
+    ///
+    /// Defaults to [`WakeMode::All`] for manual-reset wait handles,
+    /// and [`WakeMode::One`] for auto-reset wait handles.
+    pub fn wake_mode(mut self, wake: WakeMode) -> Self {
+        self.wake = Some(wake);
+        self
+    }
+
+    /// Sets what happens when the wait handle's lock have been poisoned.
+    pub fn poison_policy(mut self, policy: PoisonPolicy) -> Self {
+        self.poison = policy;
+        self
+    }
+
+    /// Sets the name of the wait handle, which is used when debugging.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Creates the wait handle pair.
+    pub fn build(self) -> (WaitHandleSignaler, WaitHandleListener) {
+        let mut handle = WaitHandle::new(self.mode).with_poison_policy(self.poison);
+        if let Some(wake) = self.wake {
+            handle.wake = wake;
+        }
+        handle.name = self.name;
+        if self.signaled {
+            handle = handle.with_initial_value(());
+        }
+        crate::create(handle)
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Wake, Waker};
+
+use crate::{WaitHandle, WaitHandleResult};
+
+type Callback = Box<dyn FnOnce() + Send>;
+
+/// Keeps a callback registered with a wait handle.
+///
+/// Dropping the guard unregisters the callback,
+/// unless it already have been run.
+#[derive(Debug)]
+#[must_use = "the callback is unregistered when the guard is dropped"]
+pub struct CallbackGuard {
+    handle: Arc<WaitHandle>,
+    registration: Option<usize>,
+}
+
+impl CallbackGuard {
+    pub(crate) fn register(handle: Arc<WaitHandle>, callback: Callback) -> WaitHandleResult<Self> {
+        let callback = Arc::new(RunCallback {
+            handle: handle.clone(),
+            signals: AtomicUsize::new(0),
+            callback: Mutex::new(Some(callback)),
+        });
+
+        let mut registration = None;
+        let registered = handle.register_unless_signaled(&mut registration, |signals| {
+            callback.signals.store(signals, Ordering::Relaxed);
+            Waker::from(callback.clone())
+        })?;
+
+        if !registered {
+            // The wait handle have already been signaled.
+            callback.run();
+        }
+
+        Ok(Self {
+            handle,
+            registration,
+        })
+    }
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        self.handle.unregister_waker(&mut self.registration);
+    }
+}
+
+/// Runs a callback when woken up by a signal.
+struct RunCallback {
+    handle: Arc<WaitHandle>,
+    signals: AtomicUsize,
+    callback: Mutex<Option<Callback>>,
+}
+
+impl RunCallback {
+    fn run(&self) {
+        let callback = self
+            .callback
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(callback) = callback {
+            callback();
+        }
+    }
+}
+
+impl Wake for RunCallback {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // The wait handle also wakes us up when it's
+        // disconnected, which shouldn't run the callback.
+        let signals = self.handle.signals.load(Ordering::Acquire);
+        if signals != self.signals.load(Ordering::Relaxed) {
+            self.run();
+        }
+    }
+}
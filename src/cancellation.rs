@@ -0,0 +1,169 @@
+use std::sync::Arc;
+use std::task::{Wake, Waker};
+use std::time::Duration;
+
+use crate::{Operation, WaitHandle, WaitHandleListener, WaitHandleResult, WaitHandleSignaler};
+
+/// A token that signals cancellation, built on top of a wait handle.
+///
+/// Child tokens are cancelled when their parent is cancelled,
+/// but can also be cancelled without affecting the parent.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use waithandle::CancellationToken;
+///
+/// let parent = CancellationToken::new();
+/// let child = parent.child_token();
+///
+/// child.cancel();
+/// assert!(!parent.is_cancelled());
+///
+/// let other = parent.child_token();
+/// parent.cancel();
+/// assert!(other.wait(Duration::from_secs(1)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    signaler: WaitHandleSignaler,
+    listener: WaitHandleListener,
+    parent: Option<Registration>,
+}
+
+// A child's registration with its parent's wait handle.
+#[derive(Debug)]
+struct Registration {
+    handle: Arc<WaitHandle>,
+    id: Option<usize>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Unregister from the parent, so that
+        // cancelled children doesn't pile up.
+        if let Some(parent) = &mut self.parent {
+            parent.handle.unregister_waker(&mut parent.id);
+        }
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new cancellation token without a parent.
+    pub fn new() -> Self {
+        let (signaler, listener) = crate::new();
+        Self {
+            inner: Arc::new(Inner {
+                signaler,
+                listener,
+                parent: None,
+            }),
+        }
+    }
+
+    /// Creates a child token, which is cancelled when this token is cancelled.
+    pub fn child_token(&self) -> Self {
+        self.try_child_token().expect("error occured while creating child token")
+    }
+
+    /// Tries to create a child token, which is cancelled when this token is cancelled.
+    pub fn try_child_token(&self) -> WaitHandleResult<Self> {
+        let (signaler, listener) = crate::new();
+        let handle = self.inner.listener.handle.clone();
+        let waker = Waker::from(Arc::new(Propagate {
+            parent: handle.clone(),
+            signaler: signaler.clone(),
+        }));
+
+        let mut id = None;
+        if handle.poll_wait(&mut id, None, &waker, Operation::Register)?.is_ready() {
+            // The parent have already been cancelled.
+            signaler.try_signal()?;
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                signaler,
+                listener,
+                parent: Some(Registration { handle, id }),
+            }),
+        })
+    }
+
+    /// Gets a listener for the token, which is signaled once it's cancelled.
+    pub fn listener(&self) -> WaitHandleListener {
+        self.inner.listener.clone()
+    }
+
+    /// Cancels the token and all of its children.
+    pub fn cancel(&self) {
+        self.try_cancel().expect("error occured while cancelling token")
+    }
+
+    /// Tries to cancel the token and all of its children.
+    pub fn try_cancel(&self) -> WaitHandleResult<()> {
+        self.inner.signaler.try_signal()
+    }
+
+    /// Checks whether or not the token have been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.try_is_cancelled().expect("an error occured while checking token")
+    }
+
+    /// Tries checking whether or not the token have been cancelled.
+    pub fn try_is_cancelled(&self) -> WaitHandleResult<bool> {
+        self.inner.listener.try_check()
+    }
+
+    /// Waits until the token have been cancelled or the timeout occur,
+    /// whichever comes first.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        self.try_wait(timeout).expect("an error occured while waiting for token")
+    }
+
+    /// Tries waiting until the token have been cancelled or the timeout occur,
+    /// whichever comes first.
+    pub fn try_wait(&self, timeout: Duration) -> WaitHandleResult<bool> {
+        self.inner.listener.try_wait(timeout)
+    }
+
+    /// Waits until the token have been cancelled, without a timeout.
+    pub fn wait_forever(&self) {
+        self.try_wait_forever().expect("an error occured while waiting for token")
+    }
+
+    /// Tries waiting until the token have been cancelled, without a timeout.
+    pub fn try_wait_forever(&self) -> WaitHandleResult<()> {
+        self.inner.listener.try_wait_forever()
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cancels a child token when woken up by its parent.
+struct Propagate {
+    parent: Arc<WaitHandle>,
+    signaler: WaitHandleSignaler,
+}
+
+impl Wake for Propagate {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // The parent also wakes us up when it's dropped,
+        // which shouldn't cancel the child.
+        if let Ok(true) = self.parent.check() {
+            let _ = self.signaler.try_signal();
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! The primitive that threads block on while waiting for a wait handle.
+//!
+//! By default, this is a [`Condvar`](std::sync::Condvar). With the `futex`
+//! feature enabled on Linux, threads block directly on an atomic word
+//! using the `futex` syscall instead.
+
+use std::sync::{LockResult, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+#[cfg(not(all(feature = "futex", target_os = "linux")))]
+pub(crate) use self::condvar::Condition;
+#[cfg(all(feature = "futex", target_os = "linux"))]
+pub(crate) use self::futex::Condition;
+
+impl Condition {
+    /// Blocks while the condition holds or until the deadline
+    /// have passed, whichever comes first.
+    pub fn wait_while<'a, T, F>(
+        &self,
+        mutex: &'a Mutex<T>,
+        mut guard: MutexGuard<'a, T>,
+        deadline: Option<Instant>,
+        mut condition: F,
+    ) -> LockResult<MutexGuard<'a, T>>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        while condition(&mut guard) {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+            guard = self.wait(mutex, guard, timeout)?;
+        }
+        Ok(guard)
+    }
+}
+
+#[cfg(not(all(feature = "futex", target_os = "linux")))]
+mod condvar {
+    use super::{Duration, LockResult, Mutex, MutexGuard};
+    use std::sync::{Condvar, PoisonError};
+
+    #[derive(Debug, Default)]
+    pub(crate) struct Condition {
+        cvar: Condvar,
+    }
+
+    impl Condition {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Blocks until notified or the timeout occur, whichever comes first.
+        /// Just like a condition variable, this might wake up spuriously.
+        pub fn wait<'a, T>(
+            &self,
+            _mutex: &'a Mutex<T>,
+            guard: MutexGuard<'a, T>,
+            timeout: Option<Duration>,
+        ) -> LockResult<MutexGuard<'a, T>> {
+            match timeout {
+                Some(timeout) => self
+                    .cvar
+                    .wait_timeout(guard, timeout)
+                    .map(|(guard, _)| guard)
+                    .map_err(|err| PoisonError::new(err.into_inner().0)),
+                None => self.cvar.wait(guard),
+            }
+        }
+
+        pub fn notify_one(&self) {
+            self.cvar.notify_one();
+        }
+
+        pub fn notify_all(&self) {
+            self.cvar.notify_all();
+        }
+    }
+}
+
+#[cfg(all(feature = "futex", target_os = "linux"))]
+mod futex {
+    use super::{Duration, LockResult, Mutex, MutexGuard};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, Default)]
+    pub(crate) struct Condition {
+        // Incremented on every notification, so that a thread
+        // never blocks after missing a notification.
+        sequence: AtomicU32,
+        // The number of threads blocked on the sequence. Unlike a
+        // condition variable, notifying without any blocked threads
+        // doesn't have to make a syscall.
+        sleepers: AtomicU32,
+    }
+
+    impl Condition {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Blocks until notified or the timeout occur, whichever comes first.
+        /// Just like a condition variable, this might wake up spuriously.
+        pub fn wait<'a, T>(
+            &self,
+            mutex: &'a Mutex<T>,
+            guard: MutexGuard<'a, T>,
+            timeout: Option<Duration>,
+        ) -> LockResult<MutexGuard<'a, T>> {
+            // The sequence must be read and the thread counted while
+            // holding the lock, since the state is changed while holding it.
+            self.sleepers.fetch_add(1, Ordering::SeqCst);
+            let sequence = self.sequence.load(Ordering::SeqCst);
+            drop(guard);
+            crate::futex::wait(&self.sequence, sequence, timeout);
+            self.sleepers.fetch_sub(1, Ordering::SeqCst);
+            mutex.lock()
+        }
+
+        pub fn notify_one(&self) {
+            self.notify(1);
+        }
+
+        pub fn notify_all(&self) {
+            self.notify(i32::MAX);
+        }
+
+        fn notify(&self, count: i32) {
+            self.sequence.fetch_add(1, Ordering::SeqCst);
+            if self.sleepers.load(Ordering::SeqCst) > 0 {
+                crate::futex::wake(&self.sequence, count);
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::{Operation, ResetMode, WaitHandle, WaitHandleError, WaitHandleResult};
+
+/// A countdown event that is signaled once it
+/// have been signaled a specific number of times.
+///
+/// ```rust
+/// use std::thread;
+/// use std::time::Duration;
+/// use waithandle::CountdownEvent;
+///
+/// let countdown = CountdownEvent::new(4);
+///
+/// for _ in 0..4 {
+///     let countdown = countdown.clone();
+///     thread::spawn(move || {
+///         println!("Doing some work...");
+///         countdown.signal();
+///     });
+/// }
+///
+/// // Wait for 5 seconds or until all workers are done
+/// if countdown.wait(Duration::from_secs(5)) {
+///     println!("All workers are done!");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CountdownEvent {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    remaining: Mutex<usize>,
+    handle: WaitHandle,
+}
+
+impl CountdownEvent {
+    /// Creates a countdown event that is signaled
+    /// after the provided number of signals.
+    pub fn new(count: usize) -> Self {
+        let handle = WaitHandle::new(ResetMode::Manual);
+        if count == 0 {
+            handle.signal().expect("new wait handle can't be poisoned");
+        }
+
+        Self {
+            inner: Arc::new(Inner {
+                remaining: Mutex::new(count),
+                handle,
+            }),
+        }
+    }
+
+    /// Gets the number of signals remaining
+    /// before the countdown event is signaled.
+    pub fn remaining(&self) -> usize {
+        self.try_remaining().expect("an error occured while checking countdown event")
+    }
+
+    /// Tries getting the number of signals remaining
+    /// before the countdown event is signaled.
+    pub fn try_remaining(&self) -> WaitHandleResult<usize> {
+        self.inner
+            .remaining
+            .lock()
+            .map(|remaining| *remaining)
+            .map_err(|err| WaitHandleError::poisoned(Operation::Check, err))
+    }
+
+    /// Registers a signal with the countdown event.
+    ///
+    /// Signals after the countdown event have been signaled are ignored.
+    pub fn signal(&self) {
+        self.try_signal().expect("error occured while signaling countdown event")
+    }
+
+    /// Tries to register a signal with the countdown event.
+    ///
+    /// Signals after the countdown event have been signaled are ignored.
+    pub fn try_signal(&self) -> WaitHandleResult<()> {
+        let mut remaining = self
+            .inner
+            .remaining
+            .lock()
+            .map_err(|err| WaitHandleError::poisoned(Operation::Signal, err))?;
+        if *remaining > 0 {
+            *remaining -= 1;
+            if *remaining == 0 {
+                return self.inner.handle.signal();
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks whether or not the countdown event have been signaled.
+    pub fn check(&self) -> bool {
+        self.try_check().expect("an error occured while checking countdown event")
+    }
+
+    /// Tries checking whether or not the countdown event have been signaled.
+    pub fn try_check(&self) -> WaitHandleResult<bool> {
+        self.inner.handle.check()
+    }
+
+    /// Waits until the countdown event have been signaled or the timeout occur,
+    /// whichever comes first.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        self.try_wait(timeout).expect("an error occured while waiting for countdown event")
+    }
+
+    /// Tries waiting until the countdown event have been signaled or the timeout occur,
+    /// whichever comes first.
+    pub fn try_wait(&self, timeout: Duration) -> WaitHandleResult<bool> {
+        self.inner.handle.wait(timeout)
+    }
+
+    /// Waits until the countdown event have been signaled, without a timeout.
+    pub fn wait_forever(&self) {
+        self.try_wait_forever().expect("an error occured while waiting for countdown event")
+    }
+
+    /// Tries waiting until the countdown event have been signaled, without a timeout.
+    pub fn try_wait_forever(&self) -> WaitHandleResult<()> {
+        self.inner.handle.wait_forever()
+    }
+}
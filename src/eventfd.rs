@@ -0,0 +1,29 @@
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+
+/// Creates a non-blocking eventfd, which is
+/// readable if the initial state is signaled.
+pub(crate) fn create(signaled: bool) -> io::Result<OwnedFd> {
+    let flags = libc::EFD_CLOEXEC | libc::EFD_NONBLOCK;
+    let fd = unsafe { libc::eventfd(signaled as libc::c_uint, flags) };
+    if fd == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Makes the eventfd readable.
+pub(crate) fn signal(fd: &OwnedFd) {
+    let value: u64 = 1;
+    unsafe {
+        libc::write(fd.as_raw_fd(), &value as *const u64 as *const libc::c_void, 8);
+    }
+}
+
+/// Makes the eventfd no longer readable.
+pub(crate) fn drain(fd: &OwnedFd) {
+    let mut value: u64 = 0;
+    unsafe {
+        libc::read(fd.as_raw_fd(), &mut value as *mut u64 as *mut libc::c_void, 8);
+    }
+}
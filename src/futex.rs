@@ -0,0 +1,58 @@
+use std::ptr;
+use std::sync::atomic::AtomicU32;
+use std::time::Duration;
+
+/// Blocks the current thread as long as the atomic word contains
+/// the expected value, until woken up or the timeout occur.
+#[cfg(feature = "futex")]
+pub(crate) fn wait(futex: &AtomicU32, expected: u32, timeout: Option<Duration>) {
+    futex_wait(futex, expected, timeout, libc::FUTEX_PRIVATE_FLAG);
+}
+
+/// Wakes up to `count` threads blocked on the atomic word.
+#[cfg(feature = "futex")]
+pub(crate) fn wake(futex: &AtomicU32, count: i32) {
+    futex_wake(futex, count, libc::FUTEX_PRIVATE_FLAG);
+}
+
+/// Blocks the current thread as long as the atomic word in shared memory
+/// contains the expected value, until woken up or the timeout occur.
+#[cfg(feature = "shm")]
+pub(crate) fn wait_shared(futex: &AtomicU32, expected: u32, timeout: Option<Duration>) {
+    futex_wait(futex, expected, timeout, 0);
+}
+
+/// Wakes up to `count` threads in any process blocked
+/// on the atomic word in shared memory.
+#[cfg(feature = "shm")]
+pub(crate) fn wake_shared(futex: &AtomicU32, count: i32) {
+    futex_wake(futex, count, 0);
+}
+
+fn futex_wait(futex: &AtomicU32, expected: u32, timeout: Option<Duration>, flags: libc::c_int) {
+    let timeout = timeout.map(|timeout| libc::timespec {
+        tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+        tv_nsec: timeout.subsec_nanos() as libc::c_long,
+    });
+    let timeout = timeout
+        .as_ref()
+        .map_or(ptr::null(), |timeout| timeout as *const libc::timespec);
+
+    // Interruptions, timeouts and value mismatches are all reported
+    // as errors, but they're handled by the caller re-checking its state.
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            futex.as_ptr(),
+            libc::FUTEX_WAIT | flags,
+            expected,
+            timeout,
+        );
+    }
+}
+
+fn futex_wake(futex: &AtomicU32, count: i32, flags: libc::c_int) {
+    unsafe {
+        libc::syscall(libc::SYS_futex, futex.as_ptr(), libc::FUTEX_WAKE | flags, count);
+    }
+}
@@ -0,0 +1,141 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+
+use crate::timer::{self, TimerKey};
+use crate::{Operation, WaitHandle, WaitHandleResult};
+
+/// A future that waits for a wait handle to be signaled.
+///
+/// Resolves to `true` if the wait handle was signaled, or `false` if
+/// the timeout occured. If the wait handle can't be signaled anymore,
+/// the future resolves to `false` once the timeout occur.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitFuture {
+    inner: TryWaitFuture,
+    disconnected: bool,
+}
+
+impl WaitFuture {
+    pub(crate) fn new(inner: TryWaitFuture) -> Self {
+        Self {
+            inner,
+            disconnected: false,
+        }
+    }
+}
+
+impl Future for WaitFuture {
+    type Output = bool;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if !this.disconnected {
+            match Pin::new(&mut this.inner).poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(err)) if err.is_disconnected() => this.disconnected = true,
+                Poll::Ready(result) => {
+                    return Poll::Ready(
+                        result.expect("an error occured while waiting for wait handle"),
+                    )
+                }
+            }
+        }
+
+        // Nothing can signal the wait handle anymore,
+        // so keep waiting until the deadline passes.
+        this.inner.poll_deadline(cx).map(|()| false)
+    }
+}
+
+/// A future that tries waiting for a wait handle to be signaled.
+///
+/// Resolves to `Ok(true)` if the wait handle was signaled,
+/// or `Ok(false)` if the timeout occured.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TryWaitFuture {
+    handle: Arc<WaitHandle>,
+    deadline: Option<Instant>,
+    registration: Option<usize>,
+    timer: Option<(TimerKey, Waker)>,
+}
+
+impl TryWaitFuture {
+    pub(crate) fn new(handle: Arc<WaitHandle>, deadline: Option<Instant>) -> Self {
+        Self {
+            handle,
+            deadline,
+            registration: None,
+            timer: None,
+        }
+    }
+}
+
+impl Future for TryWaitFuture {
+    type Output = WaitHandleResult<bool>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        match this
+            .handle
+            .poll_wait(&mut this.registration, this.deadline, cx.waker(), Operation::Wait)
+        {
+            Ok(Poll::Pending) => {}
+            Ok(Poll::Ready(value)) => {
+                this.cancel_timer();
+                return Poll::Ready(Ok(value.is_some()));
+            }
+            Err(err) => {
+                this.cancel_timer();
+                return Poll::Ready(Err(err));
+            }
+        }
+
+        // Make sure that we're woken up when the deadline passes.
+        this.register_timer(cx);
+        Poll::Pending
+    }
+}
+
+impl TryWaitFuture {
+    // Completes once the deadline have passed, or never without a deadline.
+    fn poll_deadline(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if matches!(self.deadline, Some(deadline) if deadline <= Instant::now()) {
+            self.cancel_timer();
+            return Poll::Ready(());
+        }
+        self.register_timer(cx);
+        Poll::Pending
+    }
+
+    fn register_timer(&mut self, cx: &mut Context<'_>) {
+        if let Some(deadline) = self.deadline {
+            let registered =
+                matches!(&self.timer, Some((_, waker)) if waker.will_wake(cx.waker()));
+            if !registered {
+                self.cancel_timer();
+                let key = timer::register(deadline, cx.waker().clone());
+                self.timer = Some((key, cx.waker().clone()));
+            }
+        }
+    }
+
+    // Removes the waker from the timer, so that the timer
+    // doesn't keep the task alive until the deadline.
+    fn cancel_timer(&mut self) {
+        if let Some((key, _)) = self.timer.take() {
+            timer::cancel(key);
+        }
+    }
+}
+
+impl Drop for TryWaitFuture {
+    fn drop(&mut self) {
+        self.handle.unregister_waker(&mut self.registration);
+        self.cancel_timer();
+    }
+}
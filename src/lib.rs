@@ -38,18 +38,167 @@
 use std::error;
 use std::fmt;
 use std::fmt::Formatter;
-use std::sync::{Arc, Condvar, Mutex, PoisonError};
-use std::time::Duration;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Poll, Waker};
+use std::thread;
+
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use condition::Condition;
+use stats::Waiter;
+#[cfg(feature = "tracing")]
+use trace::WaitSpan;
+
+mod barrier;
+mod builder;
+mod callback;
+mod cancellation;
+mod condition;
+mod countdown;
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+mod eventfd;
+#[cfg(feature = "async")]
+mod future;
+#[cfg(all(any(feature = "futex", feature = "shm"), target_os = "linux"))]
+mod futex;
+mod multi;
+mod payload;
+mod semaphore;
+mod stats;
+#[cfg(all(feature = "shm", target_os = "linux"))]
+pub mod shm;
+#[cfg(all(feature = "signals", target_os = "linux"))]
+pub mod signals;
+#[cfg(feature = "async")]
+mod timer;
+#[cfg(feature = "tracing")]
+mod trace;
+
+pub use barrier::{Barrier, BarrierCanceller, BarrierOutcome};
+pub use builder::WaitHandleBuilder;
+pub use callback::CallbackGuard;
+pub use cancellation::CancellationToken;
+pub use countdown::CountdownEvent;
+#[cfg(feature = "async")]
+pub use future::{TryWaitFuture, WaitFuture};
+pub use multi::{try_wait_all, try_wait_any, wait_all, wait_any};
+pub use payload::{PayloadListener, PayloadSignaler};
+pub use semaphore::{SemaphoreListener, SemaphoreSignaler};
+pub use stats::WaitHandleStats;
 
 /// The result of a wait handle operation.
 pub type WaitHandleResult<T> = std::result::Result<T, WaitHandleError>;
 
+// Dropping every signaler is part of the normal teardown, so the methods
+// that can't fail report a disconnected wait handle as not signaled.
+fn disconnected_as_unsignaled<T: Default>(result: WaitHandleResult<T>) -> WaitHandleResult<T> {
+    match result {
+        Err(err) if err.is_disconnected() => Ok(T::default()),
+        result => result,
+    }
+}
+
+// Waits that can't fail keep waiting until the deadline, just like they would
+// for a wait handle that never gets signaled. Returning right away would make
+// a caller that waits in a loop spin. Without a deadline, this never returns.
+fn disconnected_as_timed_out<T: Default>(
+    result: WaitHandleResult<T>,
+    deadline: Option<Instant>,
+) -> WaitHandleResult<T> {
+    match result {
+        Err(err) if err.is_disconnected() => {
+            match deadline {
+                Some(deadline) => thread::sleep(deadline.saturating_duration_since(Instant::now())),
+                None => loop {
+                    thread::park();
+                },
+            }
+            Ok(T::default())
+        }
+        result => result,
+    }
+}
+
 ///////////////////////////////////////////////////////////
 // Constructor
 
 /// Creates a wait handle pair for signaling and listening.
+///
+/// The wait handle is manual-reset, which means that it stays signaled
+/// until [`WaitHandleSignaler::reset`] is called.
 pub fn new() -> (WaitHandleSignaler, WaitHandleListener) {
-    let wait_handle = Arc::new(WaitHandle::new());
+    create(WaitHandle::new(ResetMode::Manual))
+}
+
+/// Creates an auto-reset wait handle pair for signaling and listening.
+///
+/// A successful wait consumes the signal, which means that each call to
+/// [`WaitHandleSignaler::signal`] releases exactly one waiter.
+pub fn new_auto_reset() -> (WaitHandleSignaler, WaitHandleListener) {
+    create(WaitHandle::new(ResetMode::Auto))
+}
+
+/// Creates a wait handle pair for signaling and listening,
+/// where the signal carries a value such as the reason for signaling.
+///
+/// The wait handle is manual-reset, which means that it stays signaled
+/// until [`PayloadSignaler::reset`] is called.
+pub fn new_with_payload<T: Clone>() -> (PayloadSignaler<T>, PayloadListener<T>) {
+    let wait_handle = Arc::new(WaitHandle::new(ResetMode::Manual));
+    let signaler = PayloadSignaler::new(wait_handle.clone());
+    let listener = PayloadListener::new(wait_handle);
+    (signaler, listener)
+}
+
+/// Creates a wait handle pair for signaling and listening,
+/// which handles a poisoned lock according to the provided policy.
+///
+/// The wait handle is manual-reset, which means that it stays signaled
+/// until [`WaitHandleSignaler::reset`] is called.
+pub fn new_with_poison_policy(policy: PoisonPolicy) -> (WaitHandleSignaler, WaitHandleListener) {
+    create(WaitHandle::new(ResetMode::Manual).with_poison_policy(policy))
+}
+
+/// Creates a wait handle pair for signaling and listening, where the signal
+/// carries a value and a poisoned lock is handled according to the provided policy.
+///
+/// A poisoned lock happens when cloning the value panics.
+pub fn new_with_payload_and_poison_policy<T: Clone>(
+    policy: PoisonPolicy,
+) -> (PayloadSignaler<T>, PayloadListener<T>) {
+    let wait_handle = Arc::new(WaitHandle::new(ResetMode::Manual).with_poison_policy(policy));
+    let signaler = PayloadSignaler::new(wait_handle.clone());
+    let listener = PayloadListener::new(wait_handle);
+    (signaler, listener)
+}
+
+/// Creates a counting semaphore pair for releasing and acquiring permits,
+/// starting out with the provided number of permits.
+pub fn semaphore(permits: usize) -> (SemaphoreSignaler, SemaphoreListener) {
+    let semaphore = Arc::new(semaphore::Semaphore::new(permits));
+    let signaler = SemaphoreSignaler::new(semaphore.clone());
+    let listener = SemaphoreListener::new(semaphore);
+    (signaler, listener)
+}
+
+/// Creates a barrier pair for cancelling and waiting, which releases
+/// the waiting participants once the provided number of participants
+/// have arrived.
+pub fn barrier(participants: usize) -> (BarrierCanceller, Barrier) {
+    let state = Arc::new(barrier::BarrierState::new(participants));
+    let canceller = BarrierCanceller::new(state.clone());
+    let barrier = Barrier::new(state);
+    (canceller, barrier)
+}
+
+fn create(handle: WaitHandle) -> (WaitHandleSignaler, WaitHandleListener) {
+    let wait_handle = Arc::new(handle);
     let signaler = WaitHandleSignaler::new(wait_handle.clone());
     let listener = WaitHandleListener::new(wait_handle);
     (signaler, listener)
@@ -58,62 +207,599 @@ pub fn new() -> (WaitHandleSignaler, WaitHandleListener) {
 ///////////////////////////////////////////////////////////
 // Wait handle
 
-#[derive(Debug, Default, Clone)]
-struct WaitHandle {
-    pair: Arc<(Mutex<bool>, Condvar)>,
+/// Decides whether or not a wait handle stays signaled after a successful wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResetMode {
+    /// The wait handle stays signaled until it's reset.
+    #[default]
+    Manual,
+    /// A successful wait consumes the signal, which resets the wait handle.
+    Auto,
 }
 
-impl WaitHandle {
-    pub fn new() -> Self {
-        let pair = Arc::new((Mutex::new(false), Condvar::new()));
-        return WaitHandle { pair };
+/// Decides how many blocked threads a signal wakes up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeMode {
+    /// Wake every thread waiting for the wait handle.
+    All,
+    /// Wake a single thread waiting for the wait handle.
+    ///
+    /// For manual-reset wait handles, the remaining threads
+    /// keep waiting until they're woken up by a later signal
+    /// or the timeout occur.
+    One,
+}
+
+impl WakeMode {
+    fn for_reset_mode(mode: ResetMode) -> Self {
+        match mode {
+            // Every clone of the listener might be blocked on the
+            // handle, so make sure that all of them are woken up.
+            ResetMode::Manual => WakeMode::All,
+            // Only one waiter can consume the signal anyway.
+            ResetMode::Auto => WakeMode::One,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State<T> {
+    value: Option<T>,
+    signalers: usize,
+    disconnected: bool,
+    wakers: Vec<(usize, Waker)>,
+    next_waker_id: usize,
+    last_signaled: Option<Instant>,
+}
+
+impl<T> State<T> {
+    fn new() -> Self {
+        State {
+            value: None,
+            signalers: 0,
+            disconnected: false,
+            wakers: Vec::new(),
+            next_waker_id: 0,
+            last_signaled: None,
+        }
     }
 
+    fn is_pending(&self) -> bool {
+        self.value.is_none() && !self.disconnected
+    }
+
+    // Describes the outcome of a wait, before the signal is consumed.
+    #[cfg(feature = "tracing")]
+    fn outcome(&self, waited: bool) -> &'static str {
+        if self.value.is_some() {
+            if waited {
+                "signaled"
+            } else {
+                "already signaled"
+            }
+        } else if self.disconnected {
+            "disconnected"
+        } else {
+            "timed out"
+        }
+    }
+}
+
+struct WaitHandle<T = ()> {
+    state: Mutex<State<T>>,
+    condition: Condition,
+    mode: ResetMode,
+    wake: WakeMode,
+    poison: PoisonPolicy,
+    name: Option<String>,
+    // Mirrors the state, so that checking the wait handle
+    // doesn't have to take the lock.
+    signaled: AtomicBool,
+    disconnected: AtomicBool,
+    // The number of times the wait handle have been signaled,
+    // including signals while it already was signaled.
+    signals: AtomicUsize,
+    // The number of times the wait handle have been reset,
+    // including resets while it wasn't signaled.
+    resets: AtomicUsize,
+    // The number of threads blocked on the wait handle.
+    waiters: AtomicUsize,
+    #[cfg(all(feature = "eventfd", target_os = "linux"))]
+    eventfd: OnceLock<OwnedFd>,
+}
+
+impl WaitHandle {
     pub fn check(&self) -> WaitHandleResult<bool> {
-        self.wait(Duration::from_micros(0))
+        if self.is_signaled() {
+            return Ok(true);
+        }
+        self.check_value().map(|value| value.is_some())
     }
 
     pub fn wait(&self, timeout: Duration) -> WaitHandleResult<bool> {
-        let (lock, cvar) = &*self.pair;
-        let mut guard = lock.lock()?;
-        let result = cvar.wait_timeout_while(guard, timeout, |&mut pending| !pending)?;
-        guard = result.0;
-        if *guard {
+        if self.is_signaled() {
+            self.trace_already_signaled();
             return Ok(true);
         }
-        Ok(false)
+        self.wait_value(timeout).map(|value| value.is_some())
     }
 
-    pub fn reset(&self) -> WaitHandleResult<()> {
-        self.set(false)
+    pub fn wait_outcome(&self, timeout: Duration) -> WaitHandleResult<WaitStatus> {
+        let start = Instant::now();
+        if self.is_signaled() {
+            self.trace_already_signaled();
+            return Ok(WaitStatus::new(WaitOutcome::AlreadySignaled, start.elapsed()));
+        }
+
+        let outcome = self.wait_for(start.checked_add(timeout), |state, waited| {
+            if self.consume(state).is_some() {
+                if waited {
+                    WaitOutcome::Signaled
+                } else {
+                    WaitOutcome::AlreadySignaled
+                }
+            } else if state.disconnected {
+                WaitOutcome::Disconnected
+            } else {
+                WaitOutcome::TimedOut
+            }
+        })?;
+        Ok(WaitStatus::new(outcome, start.elapsed()))
+    }
+
+    pub fn wait_until(&self, deadline: Instant) -> WaitHandleResult<bool> {
+        if self.is_signaled() {
+            self.trace_already_signaled();
+            return Ok(true);
+        }
+        self.wait_value_until(deadline).map(|value| value.is_some())
+    }
+
+    pub fn wait_forever(&self) -> WaitHandleResult<()> {
+        if self.is_signaled() {
+            self.trace_already_signaled();
+            return Ok(());
+        }
+        self.wait_value_forever()
     }
 
     pub fn signal(&self) -> WaitHandleResult<()> {
-        self.set(true)
+        self.set(Some(()))
+    }
+}
+
+impl<T> WaitHandle<T> {
+    pub fn new(mode: ResetMode) -> Self {
+        WaitHandle {
+            state: Mutex::new(State::new()),
+            condition: Condition::new(),
+            mode,
+            wake: WakeMode::for_reset_mode(mode),
+            poison: PoisonPolicy::Fail,
+            name: None,
+            signaled: AtomicBool::new(false),
+            disconnected: AtomicBool::new(false),
+            signals: AtomicUsize::new(0),
+            resets: AtomicUsize::new(0),
+            waiters: AtomicUsize::new(0),
+            #[cfg(all(feature = "eventfd", target_os = "linux"))]
+            eventfd: OnceLock::new(),
+        }
+    }
+
+    pub fn reset(&self) -> WaitHandleResult<()> {
+        self.set(None)
+    }
+
+    pub fn signal_with(&self, value: T) -> WaitHandleResult<()> {
+        self.set(Some(value))
+    }
+
+    fn with_poison_policy(mut self, policy: PoisonPolicy) -> Self {
+        self.poison = policy;
+        self
+    }
+
+    // Makes a new wait handle start out signaled. Nobody signaled it,
+    // so the statistics and the eventfd are left untouched; the eventfd
+    // picks up the state once it's created.
+    fn with_initial_value(mut self, value: T) -> Self {
+        let state = self.state.get_mut().unwrap_or_else(PoisonError::into_inner);
+        state.value = Some(value);
+        *self.signaled.get_mut() = true;
+        self
     }
 
-    fn set(&self, value: bool) -> WaitHandleResult<()> {
-        let (lock, cvar) = &*self.pair;
-        let mut guard = lock.lock()?;
-        if *guard != value {
-            *guard = value;
-            cvar.notify_one();
+    fn lock(&self, operation: Operation) -> WaitHandleResult<MutexGuard<'_, State<T>>> {
+        self.state
+            .lock()
+            .or_else(|err| self.recover(operation, err))
+    }
+
+    // Handles a poisoned lock according to the poison policy.
+    fn recover<G>(&self, operation: Operation, err: PoisonError<G>) -> WaitHandleResult<G> {
+        match self.poison {
+            PoisonPolicy::Fail => {
+                // Creating the error releases the lock.
+                let err = WaitHandleError::poisoned(operation, err);
+                self.trace_poisoned(operation);
+                Err(err)
+            }
+            PoisonPolicy::Recover => {
+                self.trace_poisoned(operation);
+                Ok(err.into_inner())
+            }
+            PoisonPolicy::Clear => {
+                self.trace_poisoned(operation);
+                self.state.clear_poison();
+                Ok(err.into_inner())
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn trace_poisoned(&self, operation: Operation) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            name = self.name.as_deref(),
+            operation = %operation,
+            policy = ?self.poison,
+            "wait handle lock poisoned"
+        );
+    }
+
+    fn connect(&self) {
+        let mut guard = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        guard.signalers += 1;
+    }
+
+    fn disconnect(&self) {
+        let mut guard = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        guard.signalers -= 1;
+        if guard.signalers == 0 {
+            // Nobody can signal the wait handle anymore,
+            // so wake everyone that is waiting for it.
+            guard.disconnected = true;
+            self.disconnected.store(true, Ordering::Release);
+            self.notify(guard, true);
+        }
+    }
+
+    // Checks whether or not a manual-reset wait handle
+    // have been signaled, without taking the lock.
+    fn is_signaled(&self) -> bool {
+        self.mode == ResetMode::Manual && self.signaled.load(Ordering::Acquire)
+    }
+
+    // Blocks until the wait handle is no longer pending
+    // or the deadline have passed, whichever comes first.
+    fn block<'a>(
+        &'a self,
+        mut guard: MutexGuard<'a, State<T>>,
+        deadline: Option<Instant>,
+    ) -> WaitHandleResult<MutexGuard<'a, State<T>>> {
+        let _waiter = guard.is_pending().then(|| Waiter::new(self));
+        loop {
+            // The condition is checked again after recovering from a
+            // poisoned lock, so keep waiting until it no longer holds.
+            match self
+                .condition
+                .wait_while(&self.state, guard, deadline, |state| state.is_pending())
+            {
+                Ok(guard) => return Ok(guard),
+                Err(err) => guard = self.recover(Operation::Wait, err)?,
+            }
+        }
+    }
+
+    // Blocks until the wait handle is no longer pending or the deadline have
+    // passed, and then finishes the wait while still holding the lock. Whether
+    // or not the wait actually blocked is passed along to the callback.
+    // The wait is traced without holding the lock.
+    fn wait_for<F, R>(&self, deadline: Option<Instant>, finish: F) -> WaitHandleResult<R>
+    where
+        F: FnOnce(&mut State<T>, bool) -> R,
+    {
+        #[cfg(feature = "tracing")]
+        let span = WaitSpan::enter(self.name.as_deref());
+
+        let guard = self.lock(Operation::Wait)?;
+        let waited = guard.is_pending();
+        let mut guard = self.block(guard, deadline)?;
+        #[cfg(feature = "tracing")]
+        let outcome = guard.outcome(waited);
+        let result = finish(&mut guard, waited);
+        drop(guard);
+
+        #[cfg(feature = "tracing")]
+        span.finish(outcome);
+
+        Ok(result)
+    }
+
+    // Records a wait that returned without blocking,
+    // since the wait handle already have been signaled.
+    fn trace_already_signaled(&self) {
+        #[cfg(feature = "tracing")]
+        WaitSpan::enter(self.name.as_deref()).finish("already signaled");
+    }
+
+    // Mirrors a change of the signaled state.
+    // Must be called while holding the lock.
+    fn mirror(&self, signaled: bool) {
+        self.signaled.store(signaled, Ordering::Release);
+
+        #[cfg(all(feature = "eventfd", target_os = "linux"))]
+        if let Some(fd) = self.eventfd.get() {
+            if signaled {
+                eventfd::signal(fd);
+            } else {
+                eventfd::drain(fd);
+            }
+        }
+    }
+
+    // Gets the eventfd mirroring the signaled state,
+    // creating it the first time it's requested.
+    #[cfg(all(feature = "eventfd", target_os = "linux"))]
+    fn eventfd(&self) -> std::io::Result<&OwnedFd> {
+        if let Some(fd) = self.eventfd.get() {
+            return Ok(fd);
+        }
+
+        // Hold the lock while creating the eventfd,
+        // so that no change of the state is missed.
+        let guard = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.eventfd.get().is_none() {
+            let fd = eventfd::create(guard.value.is_some())?;
+            let _ = self.eventfd.set(fd);
+        }
+        drop(guard);
+        Ok(self.eventfd.get().expect("eventfd should have been created"))
+    }
+
+    fn set(&self, value: Option<T>) -> WaitHandleResult<()> {
+        let signaled = value.is_some();
+
+        #[cfg(feature = "tracing")]
+        let _span = if signaled {
+            tracing::debug_span!("signal", name = self.name.as_deref())
+        } else {
+            tracing::debug_span!("reset", name = self.name.as_deref())
+        }
+        .entered();
+
+        let mut guard = self.lock(if signaled {
+            Operation::Signal
+        } else {
+            Operation::Reset
+        })?;
+        let was_signaled = guard.value.is_some();
+        guard.value = value;
+
+        if signaled != was_signaled {
+            self.mirror(signaled);
+        }
+        if signaled {
+            guard.last_signaled = Some(Instant::now());
+            self.signals.fetch_add(1, Ordering::Release);
+        } else {
+            self.resets.fetch_add(1, Ordering::Release);
+        }
+        if signaled && !was_signaled {
+            self.notify(guard, self.wake == WakeMode::All);
+        } else {
+            drop(guard);
+        }
+
+        // The events are emitted without holding the lock.
+        #[cfg(feature = "tracing")]
+        if signaled {
+            tracing::debug!(name = self.name.as_deref(), was_signaled, "wait handle signaled");
+        } else {
+            tracing::debug!(name = self.name.as_deref(), was_signaled, "wait handle reset");
+        }
+
+        Ok(())
+    }
+
+    fn notify(&self, mut guard: MutexGuard<'_, State<T>>, all: bool) {
+        let wakers = std::mem::take(&mut guard.wakers);
+        drop(guard);
+
+        if all {
+            self.condition.notify_all();
+        } else {
+            self.condition.notify_one();
+        }
+
+        // Wake the wakers outside of the lock, since waking
+        // a task might end up polling it right away.
+        // A panicking callback mustn't keep the remaining wakers from
+        // being woken, so the first panic is resumed once all are woken.
+        let mut panic = None;
+        for (_, waker) in wakers {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| waker.wake())) {
+                panic.get_or_insert(payload);
+            }
+        }
+        if let Some(payload) = panic {
+            panic::resume_unwind(payload);
         }
+    }
+
+    fn register_waker(
+        &self,
+        registration: &mut Option<usize>,
+        waker: &Waker,
+    ) -> WaitHandleResult<()> {
+        let mut guard = self.lock(Operation::Wait)?;
+        Self::remove_waker(&mut guard, registration);
+        Self::add_waker(&mut guard, registration, waker);
         Ok(())
     }
+
+    // Registers a waker, unless the wait handle already have been signaled.
+    // The waker is created from the number of signals so far, so that it can
+    // tell whether it was woken up by a signal or by the disconnection.
+    fn register_unless_signaled<F>(
+        &self,
+        registration: &mut Option<usize>,
+        waker: F,
+    ) -> WaitHandleResult<bool>
+    where
+        F: FnOnce(usize) -> Waker,
+    {
+        let mut guard = self.lock(Operation::Register)?;
+        if guard.value.is_some() {
+            return Ok(false);
+        }
+        let waker = waker(self.signals.load(Ordering::Acquire));
+        Self::add_waker(&mut guard, registration, &waker);
+        Ok(true)
+    }
+
+    fn unregister_waker(&self, registration: &mut Option<usize>) {
+        if let Ok(mut guard) = self.state.lock() {
+            Self::remove_waker(&mut guard, registration);
+        }
+    }
+
+    fn remove_waker(state: &mut State<T>, registration: &mut Option<usize>) {
+        if let Some(id) = registration.take() {
+            state.wakers.retain(|(other, _)| *other != id);
+        }
+    }
+
+    fn add_waker(state: &mut State<T>, registration: &mut Option<usize>, waker: &Waker) {
+        let id = state.next_waker_id;
+        state.next_waker_id = state.next_waker_id.wrapping_add(1);
+        state.wakers.push((id, waker.clone()));
+        *registration = Some(id);
+    }
+}
+
+impl<T: Clone> WaitHandle<T> {
+    pub fn check_value(&self) -> WaitHandleResult<Option<T>> {
+        // Load the disconnected flag first, since a signal
+        // always happens before the disconnection.
+        let disconnected = self.disconnected.load(Ordering::Acquire);
+        if !self.signaled.load(Ordering::Acquire) {
+            if disconnected {
+                return Err(WaitHandleError::disconnected(Operation::Check));
+            }
+            return Ok(None);
+        }
+
+        // Getting the value requires the lock.
+        let mut guard = self.lock(Operation::Check)?;
+        self.finish(&mut guard, Operation::Check)
+    }
+
+    pub fn wait_value(&self, timeout: Duration) -> WaitHandleResult<Option<T>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_value_until(deadline),
+            None => self.wait_value_forever().map(Some),
+        }
+    }
+
+    pub fn wait_value_until(&self, deadline: Instant) -> WaitHandleResult<Option<T>> {
+        self.wait_for(Some(deadline), |state, _| self.finish(state, Operation::Wait))?
+    }
+
+    pub fn wait_value_forever(&self) -> WaitHandleResult<T> {
+        self.wait_for(None, |state, _| self.finish(state, Operation::Wait))?
+            .map(|value| value.expect("wait handle should be signaled"))
+    }
+
+    fn consume(&self, state: &mut State<T>) -> Option<T> {
+        match self.mode {
+            ResetMode::Manual => state.value.clone(),
+            ResetMode::Auto => {
+                // Consume the signal.
+                let value = state.value.take();
+                if value.is_some() {
+                    self.mirror(false);
+                }
+                value
+            }
+        }
+    }
+
+    fn finish(&self, state: &mut State<T>, operation: Operation) -> WaitHandleResult<Option<T>> {
+        if let Some(value) = self.consume(state) {
+            return Ok(Some(value));
+        }
+        if state.disconnected {
+            return Err(WaitHandleError::disconnected(operation));
+        }
+        Ok(None)
+    }
+
+    fn poll_wait(
+        &self,
+        registration: &mut Option<usize>,
+        deadline: Option<Instant>,
+        waker: &Waker,
+        operation: Operation,
+    ) -> WaitHandleResult<Poll<Option<T>>> {
+        let mut guard = self.lock(operation)?;
+        let state = &mut *guard;
+
+        // Remove any previous registration, since
+        // the task might have been moved since then.
+        Self::remove_waker(state, registration);
+
+        if let Some(value) = self.consume(state) {
+            return Ok(Poll::Ready(Some(value)));
+        }
+        if state.disconnected {
+            return Err(WaitHandleError::disconnected(operation));
+        }
+        if matches!(deadline, Some(deadline) if deadline <= Instant::now()) {
+            return Ok(Poll::Ready(None));
+        }
+
+        Self::add_waker(state, registration, waker);
+        Ok(Poll::Pending)
+    }
+}
+
+impl<T> fmt::Debug for WaitHandle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let stats = WaitHandleStats::new(self);
+        f.debug_struct("WaitHandle")
+            .field("name", &stats.name())
+            .field("mode", &self.mode)
+            .field("signaled", &stats.is_signaled())
+            .field("waiters", &stats.waiters())
+            .field("signals", &stats.signals())
+            .field("resets", &stats.resets())
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for WaitHandle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&WaitHandleStats::new(self), f)
+    }
 }
 
 ///////////////////////////////////////////////////////////
 // Signaler
 
 /// The signaling half of a wait handle.
-#[derive(Debug, Clone)]
+///
+/// Once every signaler of a wait handle have been dropped,
+/// the wait handle is considered disconnected.
+#[derive(Debug)]
 pub struct WaitHandleSignaler {
     handle: Arc<WaitHandle>,
 }
 
 impl WaitHandleSignaler {
     fn new(handle: Arc<WaitHandle>) -> Self {
+        handle.connect();
         Self { handle }
     }
 
@@ -136,12 +822,40 @@ impl WaitHandleSignaler {
     pub fn try_signal(&self) -> WaitHandleResult<()> {
         self.handle.signal()
     }
+
+    /// Gets a snapshot of the wait handle's state and statistics.
+    pub fn stats(&self) -> WaitHandleStats {
+        WaitHandleStats::new(&self.handle)
+    }
+}
+
+impl fmt::Display for WaitHandleSignaler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.handle, f)
+    }
+}
+
+impl Clone for WaitHandleSignaler {
+    fn clone(&self) -> Self {
+        Self::new(self.handle.clone())
+    }
+}
+
+impl Drop for WaitHandleSignaler {
+    fn drop(&mut self) {
+        self.handle.disconnect();
+    }
 }
 
 ///////////////////////////////////////////////////////////
 // Listener
 
 /// The listening half of a wait handle.
+///
+/// If every signaler is dropped before the wait handle have been signaled,
+/// the `try_` methods fail with [`ErrorKind::Disconnected`] instead of
+/// blocking. The other methods report the wait handle as not signaled
+/// once the timeout occur, just like they would if it never got signaled.
 #[derive(Debug, Clone)]
 pub struct WaitHandleListener {
     handle: Arc<WaitHandle>,
@@ -153,8 +867,11 @@ impl WaitHandleListener {
     }
 
     /// Checks whether or not the wait handle have been signaled.
+    ///
+    /// For auto-reset wait handles, a successful check consumes the signal.
     pub fn check(&self) -> bool {
-        self.try_check().expect("an error occured while checking wait handle")
+        disconnected_as_unsignaled(self.try_check())
+            .expect("an error occured while checking wait handle")
     }
 
     /// Tries checking whether or not the wait handle have been signaled.
@@ -162,10 +879,19 @@ impl WaitHandleListener {
         self.handle.check()
     }
 
+    /// Gets a snapshot of the wait handle's state and statistics.
+    pub fn stats(&self) -> WaitHandleStats {
+        WaitHandleStats::new(&self.handle)
+    }
+
     /// Waits until the wait handle have been signaled or the timeout occur,
     /// whichever comes first.
+    ///
+    /// For auto-reset wait handles, a successful wait consumes the signal.
     pub fn wait(&self, timeout: Duration) -> bool {
-        self.try_wait(timeout).expect("an error occured while waiting for wait handle")
+        let deadline = Instant::now().checked_add(timeout);
+        disconnected_as_timed_out(self.try_wait(timeout), deadline)
+            .expect("an error occured while waiting for wait handle")
     }
 
     /// Tries waiting until the wait handle have been signaled or the timeout occur,
@@ -173,33 +899,365 @@ impl WaitHandleListener {
     pub fn try_wait(&self, timeout: Duration) -> WaitHandleResult<bool> {
         self.handle.wait(timeout)
     }
+
+    /// Waits until the wait handle have been signaled or the timeout occur,
+    /// whichever comes first, and reports how the wait ended.
+    ///
+    /// For auto-reset wait handles, a successful wait consumes the signal.
+    pub fn wait_outcome(&self, timeout: Duration) -> WaitStatus {
+        self.try_wait_outcome(timeout).expect("an error occured while waiting for wait handle")
+    }
+
+    /// Tries waiting until the wait handle have been signaled or the timeout occur,
+    /// whichever comes first, and reports how the wait ended.
+    ///
+    /// For auto-reset wait handles, a successful wait consumes the signal.
+    pub fn try_wait_outcome(&self, timeout: Duration) -> WaitHandleResult<WaitStatus> {
+        self.handle.wait_outcome(timeout)
+    }
+
+    /// Waits until the wait handle have been signaled or the deadline have passed,
+    /// whichever comes first.
+    ///
+    /// For auto-reset wait handles, a successful wait consumes the signal.
+    pub fn wait_until(&self, deadline: Instant) -> bool {
+        disconnected_as_timed_out(self.try_wait_until(deadline), Some(deadline))
+            .expect("an error occured while waiting for wait handle")
+    }
+
+    /// Tries waiting until the wait handle have been signaled or the deadline have passed,
+    /// whichever comes first.
+    ///
+    /// For auto-reset wait handles, a successful wait consumes the signal.
+    pub fn try_wait_until(&self, deadline: Instant) -> WaitHandleResult<bool> {
+        self.handle.wait_until(deadline)
+    }
+
+    /// Waits until the wait handle have been signaled, without a timeout.
+    ///
+    /// For auto-reset wait handles, the wait consumes the signal.
+    ///
+    /// If every signaler is dropped before the wait handle have been signaled,
+    /// this never returns. Use [`WaitHandleListener::try_wait_forever`]
+    /// to find out about the disconnection instead.
+    pub fn wait_forever(&self) {
+        disconnected_as_timed_out(self.try_wait_forever(), None)
+            .expect("an error occured while waiting for wait handle")
+    }
+
+    /// Tries waiting until the wait handle have been signaled, without a timeout.
+    ///
+    /// For auto-reset wait handles, the wait consumes the signal.
+    pub fn try_wait_forever(&self) -> WaitHandleResult<()> {
+        self.handle.wait_forever()
+    }
+
+    /// Registers a callback that runs once the wait handle have been signaled.
+    ///
+    /// If the wait handle already have been signaled, the callback runs right away.
+    /// Otherwise it runs on the thread signaling the wait handle. Dropping the
+    /// returned guard unregisters the callback, unless it already have been run.
+    ///
+    /// If the callback panics, the other callbacks and waiting tasks are
+    /// still woken up, after which the panic is resumed on the signaling thread.
+    pub fn on_signal<F>(&self, callback: F) -> CallbackGuard
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.try_on_signal(callback).expect("an error occured while registering callback")
+    }
+
+    /// Tries to register a callback that runs once the wait handle have been signaled.
+    ///
+    /// If the wait handle already have been signaled, the callback runs right away.
+    /// Otherwise it runs on the thread signaling the wait handle. Dropping the
+    /// returned guard unregisters the callback, unless it already have been run.
+    ///
+    /// If the callback panics, the other callbacks and waiting tasks are
+    /// still woken up, after which the panic is resumed on the signaling thread.
+    pub fn try_on_signal<F>(&self, callback: F) -> WaitHandleResult<CallbackGuard>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        CallbackGuard::register(self.handle.clone(), Box::new(callback))
+    }
+
+    /// Returns a future that completes when the wait handle have been
+    /// signaled or the timeout occur, whichever comes first.
+    ///
+    /// If no timeout is given, the future completes once the wait
+    /// handle have been signaled.
+    #[cfg(feature = "async")]
+    pub fn wait_async(&self, timeout: Option<Duration>) -> WaitFuture {
+        WaitFuture::new(self.try_wait_async(timeout))
+    }
+
+    /// Returns a future that tries waiting until the wait handle have been
+    /// signaled or the timeout occur, whichever comes first.
+    ///
+    /// If no timeout is given, the future completes once the wait
+    /// handle have been signaled.
+    #[cfg(feature = "async")]
+    pub fn try_wait_async(&self, timeout: Option<Duration>) -> TryWaitFuture {
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        TryWaitFuture::new(self.handle.clone(), deadline)
+    }
+}
+
+impl fmt::Display for WaitHandleListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.handle, f)
+    }
+}
+
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+impl WaitHandleListener {
+    /// Tries getting an `eventfd` that is readable while the wait handle
+    /// is signaled, so that it can be watched by a reactor such as
+    /// `epoll` or `mio`. The `eventfd` is created the first time it's requested.
+    ///
+    /// Don't read from the file descriptor directly. Instead, call
+    /// [`WaitHandleListener::check`] once it's readable.
+    pub fn try_eventfd(&self) -> std::io::Result<BorrowedFd<'_>> {
+        self.handle.eventfd().map(|fd| fd.as_fd())
+    }
+}
+
+/// With the `eventfd` feature enabled, the listener exposes an `eventfd`
+/// that is readable while the wait handle is signaled, so that it can be
+/// watched by a reactor such as `epoll` or `mio`.
+///
+/// Don't read from the file descriptor directly. Instead, call
+/// [`WaitHandleListener::check`] once it's readable.
+///
+/// # Panics
+///
+/// Panics if the `eventfd` can't be created. Use
+/// [`WaitHandleListener::try_eventfd`] to handle the error instead.
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+impl AsFd for WaitHandleListener {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.try_eventfd()
+            .expect("an error occured while creating eventfd for wait handle")
+    }
+}
+
+#[cfg(all(feature = "eventfd", target_os = "linux"))]
+impl AsRawFd for WaitHandleListener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.as_fd().as_raw_fd()
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Outcomes
+
+/// Describes how a wait ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitOutcome {
+    /// The wait handle was signaled while waiting.
+    Signaled,
+    /// The wait handle was already signaled when the wait started.
+    AlreadySignaled,
+    /// The timeout occured before the wait handle was signaled.
+    TimedOut,
+    /// Every signaler was dropped before the wait handle was signaled.
+    Disconnected,
+}
+
+/// The status of a completed wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitStatus {
+    outcome: WaitOutcome,
+    elapsed: Duration,
+}
+
+impl WaitStatus {
+    fn new(outcome: WaitOutcome, elapsed: Duration) -> Self {
+        Self { outcome, elapsed }
+    }
+
+    /// Gets how the wait ended.
+    pub fn outcome(&self) -> WaitOutcome {
+        self.outcome
+    }
+
+    /// Gets the time spent waiting.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Checks whether or not the wait handle was signaled.
+    pub fn is_signaled(&self) -> bool {
+        matches!(
+            self.outcome,
+            WaitOutcome::Signaled | WaitOutcome::AlreadySignaled
+        )
+    }
+
+    /// Converts the status into a result, which fails if the
+    /// wait timed out or the wait handle was disconnected.
+    pub fn into_result(self) -> WaitHandleResult<Duration> {
+        match self.outcome {
+            WaitOutcome::Signaled | WaitOutcome::AlreadySignaled => Ok(self.elapsed),
+            WaitOutcome::TimedOut => Err(WaitHandleError::timed_out(Operation::Wait)),
+            WaitOutcome::Disconnected => Err(WaitHandleError::disconnected(Operation::Wait)),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Poisoning
+
+/// Decides what happens when a wait handle's lock have been poisoned,
+/// which happens if a thread panics while holding the lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoisonPolicy {
+    /// Fail with [`ErrorKind::Poisoned`] for as long as the lock is poisoned.
+    #[default]
+    Fail,
+    /// Ignore the poisoning and keep using the lock.
+    Recover,
+    /// Ignore the poisoning and clear it, so that the lock is no longer poisoned.
+    Clear,
 }
 
 ///////////////////////////////////////////////////////////
 // Errors
 
+/// The operation that a wait handle error occured during.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Signaling the wait handle.
+    Signal,
+    /// Resetting the wait handle.
+    Reset,
+    /// Waiting for the wait handle to be signaled.
+    Wait,
+    /// Checking whether or not the wait handle have been signaled.
+    Check,
+    /// Registering interest in the wait handle being signaled.
+    Register,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Signal => write!(f, "signal"),
+            Operation::Reset => write!(f, "reset"),
+            Operation::Wait => write!(f, "wait for"),
+            Operation::Check => write!(f, "check"),
+            Operation::Register => write!(f, "register with"),
+        }
+    }
+}
+
+/// The kind of a wait handle error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A thread panicked while holding the wait handle's lock.
+    Poisoned,
+    /// Every signaler of the wait handle have been dropped.
+    Disconnected,
+    /// The timeout occured before the wait handle was signaled.
+    TimedOut,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Poisoned => write!(f, "lock poisoned"),
+            ErrorKind::Disconnected => write!(f, "disconnected"),
+            ErrorKind::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
 /// Represents a wait handle error.
+///
+/// The error records the [`Operation`] that failed, and the
+/// [`ErrorKind`] of the failure.
 #[derive(Debug, Clone)]
-pub enum WaitHandleError {
-    LockPoisoned,
+pub struct WaitHandleError {
+    operation: Operation,
+    kind: ErrorKind,
+    source: Option<PoisonedLock>,
+}
+
+impl WaitHandleError {
+    pub(crate) fn poisoned<T>(operation: Operation, err: PoisonError<T>) -> Self {
+        Self {
+            operation,
+            kind: ErrorKind::Poisoned,
+            source: Some(PoisonedLock(err.to_string())),
+        }
+    }
+
+    pub(crate) fn disconnected(operation: Operation) -> Self {
+        Self {
+            operation,
+            kind: ErrorKind::Disconnected,
+            source: None,
+        }
+    }
+
+    pub(crate) fn timed_out(operation: Operation) -> Self {
+        Self {
+            operation,
+            kind: ErrorKind::TimedOut,
+            source: None,
+        }
+    }
+
+    /// Gets the operation that failed.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// Gets the kind of the failure.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Checks whether or not the error was caused by a poisoned lock.
+    pub fn is_poisoned(&self) -> bool {
+        self.kind == ErrorKind::Poisoned
+    }
+
+    /// Checks whether or not the error was caused by the wait handle being disconnected.
+    pub fn is_disconnected(&self) -> bool {
+        self.kind == ErrorKind::Disconnected
+    }
+
+    /// Checks whether or not the error was caused by a timeout.
+    pub fn is_timed_out(&self) -> bool {
+        self.kind == ErrorKind::TimedOut
+    }
 }
 
 impl fmt::Display for WaitHandleError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            WaitHandleError::LockPoisoned => write!(f, "wait handle lock poisoned"),
-        }
+        write!(f, "failed to {} wait handle: {}", self.operation, self.kind)
     }
 }
 
 impl error::Error for WaitHandleError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-        None
+        self.source
+            .as_ref()
+            .map(|source| source as &(dyn error::Error + 'static))
     }
 }
 
-impl<T> From<PoisonError<T>> for WaitHandleError {
-    fn from(_: PoisonError<T>) -> Self {
-        WaitHandleError::LockPoisoned
+// The poisoning of a lock. The original `PoisonError` holds
+// on to the lock guard, so only its message is kept around.
+#[derive(Debug, Clone)]
+struct PoisonedLock(String);
+
+impl fmt::Display for PoisonedLock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
     }
 }
+
+impl error::Error for PoisonedLock {}
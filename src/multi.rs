@@ -0,0 +1,204 @@
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::task::{Poll, Wake, Waker};
+use std::time::{Duration, Instant};
+
+use crate::stats::Waiter;
+use crate::{
+    disconnected_as_timed_out, Operation, WaitHandle, WaitHandleError, WaitHandleListener,
+    WaitHandleResult,
+};
+
+/// Waits until any of the wait handles have been signaled or the timeout occur,
+/// whichever comes first.
+///
+/// Returns the index of the first signaled wait handle, or `None` if the
+/// timeout occured. Wait handles that can't be signaled anymore are treated
+/// like wait handles that never get signaled.
+///
+/// For auto-reset wait handles, only the returned wait handle's signal is consumed.
+pub fn wait_any(listeners: &[&WaitHandleListener], timeout: Duration) -> Option<usize> {
+    let deadline = Instant::now().checked_add(timeout);
+    disconnected_as_timed_out(try_wait_any(listeners, timeout), deadline)
+        .expect("an error occured while waiting for wait handles")
+}
+
+/// Tries waiting until any of the wait handles have been signaled or the timeout occur,
+/// whichever comes first.
+///
+/// Returns the index of the first signaled wait handle,
+/// or `None` if the timeout occured. Fails with
+/// [`ErrorKind::Disconnected`](crate::ErrorKind::Disconnected)
+/// once none of the wait handles can be signaled anymore.
+///
+/// For auto-reset wait handles, only the returned wait handle's signal is consumed.
+pub fn try_wait_any(
+    listeners: &[&WaitHandleListener],
+    timeout: Duration,
+) -> WaitHandleResult<Option<usize>> {
+    let deadline = Instant::now().checked_add(timeout);
+    let mut registrations = Registrations::new(listeners);
+    let parker = Arc::new(Parker::default());
+    let waker = Waker::from(parker.clone());
+
+    loop {
+        let mut disconnected = 0;
+        for (index, listener) in listeners.iter().enumerate() {
+            let registration = &mut registrations.ids[index];
+            match listener.handle.poll_wait(registration, None, &waker, Operation::Wait) {
+                Ok(Poll::Ready(_)) => return Ok(Some(index)),
+                Ok(Poll::Pending) => {}
+                Err(err) if err.is_disconnected() => disconnected += 1,
+                Err(err) => return Err(err),
+            }
+        }
+
+        if !listeners.is_empty() && disconnected == listeners.len() {
+            return Err(WaitHandleError::disconnected(Operation::Wait));
+        }
+        if !park(&parker, listeners, deadline) {
+            return Ok(None);
+        }
+    }
+}
+
+/// Waits until all of the wait handles have been signaled or the timeout occur,
+/// whichever comes first.
+///
+/// Returns `false` if the timeout occured. Wait handles that can't be signaled
+/// anymore are treated like wait handles that never get signaled.
+///
+/// For auto-reset wait handles, the signals are only consumed
+/// once all of the wait handles have been signaled.
+pub fn wait_all(listeners: &[&WaitHandleListener], timeout: Duration) -> bool {
+    let deadline = Instant::now().checked_add(timeout);
+    disconnected_as_timed_out(try_wait_all(listeners, timeout), deadline)
+        .expect("an error occured while waiting for wait handles")
+}
+
+/// Tries waiting until all of the wait handles have been signaled or the timeout occur,
+/// whichever comes first.
+///
+/// Fails with [`ErrorKind::Disconnected`](crate::ErrorKind::Disconnected)
+/// if any of the wait handles can't be signaled anymore.
+///
+/// For auto-reset wait handles, the signals are only consumed
+/// once all of the wait handles have been signaled.
+pub fn try_wait_all(listeners: &[&WaitHandleListener], timeout: Duration) -> WaitHandleResult<bool> {
+    let deadline = Instant::now().checked_add(timeout);
+    let mut registrations = Registrations::new(listeners);
+    let parker = Arc::new(Parker::default());
+    let waker = Waker::from(parker.clone());
+
+    // Lock the wait handles in a consistent order to avoid deadlocks
+    // with other threads waiting for the same wait handles.
+    let mut handles: Vec<&WaitHandle> = listeners.iter().map(|l| &*l.handle).collect();
+    handles.sort_by_key(|handle| *handle as *const WaitHandle);
+    handles.dedup_by_key(|handle| *handle as *const WaitHandle);
+
+    loop {
+        // Register before checking, so no signal can slip through
+        // between checking the wait handles and parking the thread.
+        for (index, listener) in listeners.iter().enumerate() {
+            let registration = &mut registrations.ids[index];
+            listener.handle.register_waker(registration, &waker)?;
+        }
+
+        if consume_all(&handles)? {
+            return Ok(true);
+        }
+        if !park(&parker, listeners, deadline) {
+            return Ok(false);
+        }
+    }
+}
+
+fn consume_all(handles: &[&WaitHandle]) -> WaitHandleResult<bool> {
+    let mut guards = Vec::with_capacity(handles.len());
+    for handle in handles {
+        guards.push(handle.lock(Operation::Wait)?);
+    }
+
+    if guards.iter().all(|state| state.value.is_some()) {
+        for (handle, state) in handles.iter().zip(guards.iter_mut()) {
+            handle.consume(state);
+        }
+        return Ok(true);
+    }
+    // A wait handle that was signaled before its signalers were
+    // dropped can still be consumed, so it's not disconnected.
+    if guards.iter().any(|state| state.disconnected && state.value.is_none()) {
+        return Err(WaitHandleError::disconnected(Operation::Wait));
+    }
+    Ok(false)
+}
+
+// Parks the thread, while counting it as waiting for each of the wait handles.
+fn park(parker: &Parker, listeners: &[&WaitHandleListener], deadline: Option<Instant>) -> bool {
+    let _waiters: Vec<_> = listeners.iter().map(|l| Waiter::new(&*l.handle)).collect();
+    parker.park(deadline)
+}
+
+/// Keeps track of the wakers registered with each
+/// wait handle, and removes them once dropped.
+struct Registrations<'a> {
+    listeners: &'a [&'a WaitHandleListener],
+    ids: Vec<Option<usize>>,
+}
+
+impl<'a> Registrations<'a> {
+    fn new(listeners: &'a [&'a WaitHandleListener]) -> Self {
+        let ids = vec![None; listeners.len()];
+        Self { listeners, ids }
+    }
+}
+
+impl Drop for Registrations<'_> {
+    fn drop(&mut self) {
+        for (listener, registration) in self.listeners.iter().zip(self.ids.iter_mut()) {
+            listener.handle.unregister_waker(registration);
+        }
+    }
+}
+
+/// Parks a thread until any of the wait handles wakes it up.
+#[derive(Default)]
+struct Parker {
+    notified: Mutex<bool>,
+    cvar: Condvar,
+}
+
+impl Parker {
+    /// Parks the current thread until woken up or the deadline have passed.
+    /// Returns `false` if the deadline have passed.
+    fn park(&self, deadline: Option<Instant>) -> bool {
+        let mut notified = self.notified.lock().unwrap_or_else(PoisonError::into_inner);
+        while !*notified {
+            notified = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return false;
+                    }
+                    self.cvar
+                        .wait_timeout(notified, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self.cvar.wait(notified).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+        *notified = false;
+        true
+    }
+}
+
+impl Wake for Parker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        *self.notified.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        self.cvar.notify_one();
+    }
+}
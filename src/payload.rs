@@ -0,0 +1,147 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{disconnected_as_timed_out, disconnected_as_unsignaled, WaitHandle, WaitHandleResult};
+
+///////////////////////////////////////////////////////////
+// Signaler
+
+/// The signaling half of a wait handle carrying a value.
+///
+/// Once every signaler of a wait handle have been dropped,
+/// the wait handle is considered disconnected.
+#[derive(Debug)]
+pub struct PayloadSignaler<T> {
+    handle: Arc<WaitHandle<T>>,
+}
+
+impl<T> PayloadSignaler<T> {
+    pub(crate) fn new(handle: Arc<WaitHandle<T>>) -> Self {
+        handle.connect();
+        Self { handle }
+    }
+
+    /// Resets the wait handle
+    pub fn reset(&self) {
+        self.try_reset().expect("error occured while resetting wait handle")
+    }
+
+    /// Tries to reset the wait handle
+    pub fn try_reset(&self) -> WaitHandleResult<()> {
+        self.handle.reset()
+    }
+
+    /// Signals the wait handle with the provided value.
+    ///
+    /// If the wait handle is already signaled, the value replaces the previous one.
+    pub fn signal_with(&self, value: T) {
+        self.try_signal_with(value).expect("error occured while signaling wait handle")
+    }
+
+    /// Tries to signal the wait handle with the provided value.
+    ///
+    /// If the wait handle is already signaled, the value replaces the previous one.
+    pub fn try_signal_with(&self, value: T) -> WaitHandleResult<()> {
+        self.handle.signal_with(value)
+    }
+}
+
+impl<T> Clone for PayloadSignaler<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.handle.clone())
+    }
+}
+
+impl<T> Drop for PayloadSignaler<T> {
+    fn drop(&mut self) {
+        self.handle.disconnect();
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Listener
+
+/// The listening half of a wait handle carrying a value.
+///
+/// If every signaler is dropped before the wait handle have been signaled,
+/// the `try_` methods fail with [`ErrorKind::Disconnected`](crate::ErrorKind::Disconnected)
+/// instead of blocking. The other methods return `None`,
+/// except for [`PayloadListener::wait_forever`] which panics.
+#[derive(Debug)]
+pub struct PayloadListener<T> {
+    handle: Arc<WaitHandle<T>>,
+}
+
+impl<T> PayloadListener<T> {
+    pub(crate) fn new(handle: Arc<WaitHandle<T>>) -> Self {
+        Self { handle }
+    }
+}
+
+impl<T: Clone> PayloadListener<T> {
+    /// Gets the value of the wait handle, if it have been signaled.
+    pub fn check(&self) -> Option<T> {
+        disconnected_as_unsignaled(self.try_check())
+            .expect("an error occured while checking wait handle")
+    }
+
+    /// Tries getting the value of the wait handle, if it have been signaled.
+    pub fn try_check(&self) -> WaitHandleResult<Option<T>> {
+        self.handle.check_value()
+    }
+
+    /// Waits until the wait handle have been signaled or the timeout occur,
+    /// whichever comes first.
+    ///
+    /// Returns the value of the wait handle, or `None` if the timeout occured.
+    pub fn wait(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now().checked_add(timeout);
+        disconnected_as_timed_out(self.try_wait(timeout), deadline)
+            .expect("an error occured while waiting for wait handle")
+    }
+
+    /// Tries waiting until the wait handle have been signaled or the timeout occur,
+    /// whichever comes first.
+    ///
+    /// Returns the value of the wait handle, or `None` if the timeout occured.
+    pub fn try_wait(&self, timeout: Duration) -> WaitHandleResult<Option<T>> {
+        self.handle.wait_value(timeout)
+    }
+
+    /// Waits until the wait handle have been signaled or the deadline have passed,
+    /// whichever comes first.
+    ///
+    /// Returns the value of the wait handle, or `None` if the deadline passed.
+    pub fn wait_until(&self, deadline: Instant) -> Option<T> {
+        disconnected_as_timed_out(self.try_wait_until(deadline), Some(deadline))
+            .expect("an error occured while waiting for wait handle")
+    }
+
+    /// Tries waiting until the wait handle have been signaled or the deadline have passed,
+    /// whichever comes first.
+    ///
+    /// Returns the value of the wait handle, or `None` if the deadline passed.
+    pub fn try_wait_until(&self, deadline: Instant) -> WaitHandleResult<Option<T>> {
+        self.handle.wait_value_until(deadline)
+    }
+
+    /// Waits until the wait handle have been signaled, without a timeout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every signaler is dropped before the wait handle have been signaled.
+    pub fn wait_forever(&self) -> T {
+        self.try_wait_forever().expect("an error occured while waiting for wait handle")
+    }
+
+    /// Tries waiting until the wait handle have been signaled, without a timeout.
+    pub fn try_wait_forever(&self) -> WaitHandleResult<T> {
+        self.handle.wait_value_forever()
+    }
+}
+
+impl<T> Clone for PayloadListener<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.handle.clone())
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::condition::Condition;
+use crate::{disconnected_as_timed_out, Operation, WaitHandleError, WaitHandleResult};
+
+///////////////////////////////////////////////////////////
+// Semaphore
+
+#[derive(Debug)]
+struct State {
+    permits: usize,
+    signalers: usize,
+    disconnected: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct Semaphore {
+    state: Mutex<State>,
+    condition: Condition,
+}
+
+impl Semaphore {
+    pub fn new(permits: usize) -> Self {
+        Semaphore {
+            state: Mutex::new(State {
+                permits,
+                signalers: 0,
+                disconnected: false,
+            }),
+            condition: Condition::new(),
+        }
+    }
+
+    pub fn acquire(&self, timeout: Duration) -> WaitHandleResult<bool> {
+        let deadline = Instant::now().checked_add(timeout);
+        let guard = self.lock(Operation::Wait)?;
+        let mut guard = self
+            .condition
+            .wait_while(&self.state, guard, deadline, |state| {
+                state.permits == 0 && !state.disconnected
+            })
+            .map_err(|err| WaitHandleError::poisoned(Operation::Wait, err))?;
+
+        if guard.permits > 0 {
+            guard.permits -= 1;
+            return Ok(true);
+        }
+        if guard.disconnected {
+            return Err(WaitHandleError::disconnected(Operation::Wait));
+        }
+        Ok(false)
+    }
+
+    pub fn release(&self) -> WaitHandleResult<()> {
+        let mut guard = self.lock(Operation::Signal)?;
+        guard.permits += 1;
+        drop(guard);
+
+        // Only one waiter can acquire the permit anyway.
+        self.condition.notify_one();
+        Ok(())
+    }
+
+    pub fn available(&self) -> WaitHandleResult<usize> {
+        Ok(self.lock(Operation::Check)?.permits)
+    }
+
+    fn lock(&self, operation: Operation) -> WaitHandleResult<MutexGuard<'_, State>> {
+        self.state
+            .lock()
+            .map_err(|err| WaitHandleError::poisoned(operation, err))
+    }
+
+    fn connect(&self) {
+        let mut guard = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        guard.signalers += 1;
+    }
+
+    fn disconnect(&self) {
+        let mut guard = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        guard.signalers -= 1;
+        if guard.signalers == 0 {
+            // Nobody can release permits anymore,
+            // so wake everyone that is waiting for one.
+            guard.disconnected = true;
+            drop(guard);
+            self.condition.notify_all();
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Signaler
+
+/// The releasing half of a counting semaphore.
+///
+/// Once every signaler of a semaphore have been dropped,
+/// the semaphore is considered disconnected.
+#[derive(Debug)]
+pub struct SemaphoreSignaler {
+    semaphore: Arc<Semaphore>,
+}
+
+impl SemaphoreSignaler {
+    pub(crate) fn new(semaphore: Arc<Semaphore>) -> Self {
+        semaphore.connect();
+        Self { semaphore }
+    }
+
+    /// Releases a permit
+    pub fn release(&self) {
+        self.try_release().expect("error occured while releasing semaphore permit")
+    }
+
+    /// Tries to release a permit
+    pub fn try_release(&self) -> WaitHandleResult<()> {
+        self.semaphore.release()
+    }
+}
+
+impl Clone for SemaphoreSignaler {
+    fn clone(&self) -> Self {
+        Self::new(self.semaphore.clone())
+    }
+}
+
+impl Drop for SemaphoreSignaler {
+    fn drop(&mut self) {
+        self.semaphore.disconnect();
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Listener
+
+/// The acquiring half of a counting semaphore.
+///
+/// If every signaler is dropped while no permits are available,
+/// the `try_` methods fail with [`ErrorKind::Disconnected`](crate::ErrorKind::Disconnected)
+/// instead of blocking, and [`SemaphoreListener::acquire`] returns `false`.
+#[derive(Debug, Clone)]
+pub struct SemaphoreListener {
+    semaphore: Arc<Semaphore>,
+}
+
+impl SemaphoreListener {
+    pub(crate) fn new(semaphore: Arc<Semaphore>) -> Self {
+        Self { semaphore }
+    }
+
+    /// Gets the number of available permits.
+    pub fn available(&self) -> usize {
+        self.try_available().expect("an error occured while checking semaphore")
+    }
+
+    /// Tries getting the number of available permits.
+    pub fn try_available(&self) -> WaitHandleResult<usize> {
+        self.semaphore.available()
+    }
+
+    /// Waits until a permit have been acquired or the timeout occur,
+    /// whichever comes first.
+    pub fn acquire(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now().checked_add(timeout);
+        disconnected_as_timed_out(self.try_acquire(timeout), deadline)
+            .expect("an error occured while acquiring semaphore permit")
+    }
+
+    /// Tries waiting until a permit have been acquired or the timeout occur,
+    /// whichever comes first.
+    pub fn try_acquire(&self, timeout: Duration) -> WaitHandleResult<bool> {
+        self.semaphore.acquire(timeout)
+    }
+}
@@ -0,0 +1,287 @@
+//! Wait handles shared between processes.
+//!
+//! The state of the wait handle lives in a shared memory mapping,
+//! and waiting processes block on it using a process-shared `futex`.
+//!
+//! Unlike wait handles within a process, a shared wait handle is never
+//! considered disconnected, since a process might exit without
+//! dropping its signaler.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! // In the supervisor process
+//! let (signaler, _) = waithandle::shm::create("/my-worker-shutdown")?;
+//!
+//! // In the worker process
+//! let (_, listener) = waithandle::shm::open("/my-worker-shutdown")?;
+//! if listener.wait(Duration::from_secs(5)) {
+//!     println!("signal received");
+//! }
+//!
+//! // Once done, remove the name again
+//! waithandle::shm::unlink("/my-worker-shutdown")?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::ffi::CString;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const SIGNALED: u32 = 1;
+
+/// Creates a named wait handle shared between processes.
+///
+/// Fails if a shared wait handle with the same name already exists.
+pub fn create(name: &str) -> io::Result<(SharedSignaler, SharedListener)> {
+    let name = shm_name(name)?;
+    let fd = cvt(unsafe {
+        libc::shm_open(
+            name.as_ptr(),
+            libc::O_RDWR | libc::O_CREAT | libc::O_EXCL | libc::O_CLOEXEC,
+            0o600,
+        )
+    })?;
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    Mapping::create(fd).map(split).inspect_err(|_| {
+        // Don't leave the name behind, since it
+        // would make creating it again fail.
+        unsafe { libc::shm_unlink(name.as_ptr()) };
+    })
+}
+
+/// Opens a named wait handle shared between processes,
+/// which have been created by [`create`].
+pub fn open(name: &str) -> io::Result<(SharedSignaler, SharedListener)> {
+    let name = shm_name(name)?;
+    let fd = cvt(unsafe { libc::shm_open(name.as_ptr(), libc::O_RDWR | libc::O_CLOEXEC, 0) })?;
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    Mapping::open(fd).map(split)
+}
+
+/// Removes the name of a shared wait handle.
+///
+/// Processes that already have opened the wait handle can keep using it.
+pub fn unlink(name: &str) -> io::Result<()> {
+    let name = shm_name(name)?;
+    cvt(unsafe { libc::shm_unlink(name.as_ptr()) }).map(|_| ())
+}
+
+/// Creates an anonymous wait handle shared between processes,
+/// backed by a `memfd` file descriptor.
+///
+/// Share the file descriptor with another process, for example by
+/// passing it over a Unix socket, and open it there using [`from_fd`].
+pub fn anonymous() -> io::Result<(SharedSignaler, SharedListener)> {
+    let name = CString::new("waithandle").expect("name contains no nul bytes");
+    let fd = cvt(unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC) })?;
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    Mapping::create(fd).map(split)
+}
+
+/// Opens a wait handle shared between processes
+/// from a file descriptor created by [`anonymous`].
+pub fn from_fd(fd: OwnedFd) -> io::Result<(SharedSignaler, SharedListener)> {
+    Mapping::open(fd).map(split)
+}
+
+fn split(mapping: Mapping) -> (SharedSignaler, SharedListener) {
+    let mapping = Arc::new(mapping);
+    let signaler = SharedSignaler {
+        mapping: mapping.clone(),
+    };
+    let listener = SharedListener { mapping };
+    (signaler, listener)
+}
+
+fn shm_name(name: &str) -> io::Result<CString> {
+    let name = if name.starts_with('/') {
+        name.to_owned()
+    } else {
+        format!("/{}", name)
+    };
+    CString::new(name).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+}
+
+fn cvt(result: libc::c_int) -> io::Result<libc::c_int> {
+    if result == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(result)
+}
+
+///////////////////////////////////////////////////////////
+// Mapping
+
+/// The shared memory mapping containing the state of the wait handle.
+#[derive(Debug)]
+struct Mapping {
+    fd: OwnedFd,
+    state: *const AtomicU32,
+}
+
+// The mapping only contains an atomic word,
+// which can be shared between threads.
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    fn create(fd: OwnedFd) -> io::Result<Self> {
+        let size = mem::size_of::<AtomicU32>() as libc::off_t;
+        cvt(unsafe { libc::ftruncate(fd.as_raw_fd(), size) })?;
+        Self::open(fd)
+    }
+
+    fn open(fd: OwnedFd) -> io::Result<Self> {
+        let mut stat = unsafe { mem::zeroed::<libc::stat>() };
+        cvt(unsafe { libc::fstat(fd.as_raw_fd(), &mut stat) })?;
+        if (stat.st_size as usize) < mem::size_of::<AtomicU32>() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "shared memory is too small to contain a wait handle",
+            ));
+        }
+
+        let state = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                mem::size_of::<AtomicU32>(),
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if state == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            fd,
+            state: state as *const AtomicU32,
+        })
+    }
+
+    fn state(&self) -> &AtomicU32 {
+        unsafe { &*self.state }
+    }
+
+    fn signal(&self) {
+        if self.state().swap(SIGNALED, Ordering::Release) != SIGNALED {
+            crate::futex::wake_shared(self.state(), i32::MAX);
+        }
+    }
+
+    fn reset(&self) {
+        self.state().store(0, Ordering::Release);
+    }
+
+    fn check(&self) -> bool {
+        self.state().load(Ordering::Acquire) == SIGNALED
+    }
+
+    fn wait(&self, deadline: Option<Instant>) -> bool {
+        loop {
+            let state = self.state().load(Ordering::Acquire);
+            if state == SIGNALED {
+                return true;
+            }
+
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return false;
+                    }
+                    Some(deadline - now)
+                }
+                None => None,
+            };
+            crate::futex::wait_shared(self.state(), state, timeout);
+        }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.state as *mut libc::c_void, mem::size_of::<AtomicU32>());
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Signaler
+
+/// The signaling half of a wait handle shared between processes.
+#[derive(Debug, Clone)]
+pub struct SharedSignaler {
+    mapping: Arc<Mapping>,
+}
+
+impl SharedSignaler {
+    /// Resets the wait handle
+    pub fn reset(&self) {
+        self.mapping.reset()
+    }
+
+    /// Signals the wait handle
+    pub fn signal(&self) {
+        self.mapping.signal()
+    }
+}
+
+/// The signaler exposes the file descriptor of the shared memory, so that
+/// it can be handed off to another process and opened there using [`from_fd`].
+///
+/// The file descriptor is always readable, so it can't be watched by
+/// a reactor such as `epoll` to find out when the wait handle is signaled.
+impl AsFd for SharedSignaler {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.mapping.fd.as_fd()
+    }
+}
+
+impl AsRawFd for SharedSignaler {
+    fn as_raw_fd(&self) -> RawFd {
+        self.mapping.fd.as_raw_fd()
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Listener
+
+/// The listening half of a wait handle shared between processes.
+#[derive(Debug, Clone)]
+pub struct SharedListener {
+    mapping: Arc<Mapping>,
+}
+
+impl SharedListener {
+    /// Checks whether or not the wait handle have been signaled.
+    pub fn check(&self) -> bool {
+        self.mapping.check()
+    }
+
+    /// Waits until the wait handle have been signaled or the timeout occur,
+    /// whichever comes first.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        self.mapping.wait(Instant::now().checked_add(timeout))
+    }
+
+    /// Waits until the wait handle have been signaled or the deadline have passed,
+    /// whichever comes first.
+    pub fn wait_until(&self, deadline: Instant) -> bool {
+        self.mapping.wait(Some(deadline))
+    }
+
+    /// Waits until the wait handle have been signaled, without a timeout.
+    pub fn wait_forever(&self) {
+        self.mapping.wait(None);
+    }
+}
@@ -0,0 +1,210 @@
+//! Bridges Unix signals such as `SIGINT` and `SIGTERM` to wait handles.
+//!
+//! The signal handler only writes the signal number to a pipe, which
+//! is async-signal-safe. A background thread reads from the pipe and
+//! signals the wait handles listening for the received signal.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! let listener = waithandle::signals::listen(&[libc::SIGINT, libc::SIGTERM])?;
+//!
+//! while !listener.check() {
+//!     println!("Doing some work...");
+//!
+//!     // Wait for 1 second or until we receive SIGINT or SIGTERM
+//!     if listener.wait(Duration::from_secs(1)) {
+//!         println!("Shutting down gracefully...");
+//!     }
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::collections::HashSet;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, Weak};
+use std::thread;
+
+use crate::{ResetMode, WaitHandle, WaitHandleListener};
+
+// The write end of the pipe, used by the signal handler.
+static WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+#[derive(Default)]
+struct Registry {
+    installed: HashSet<libc::c_int>,
+    // The registry doesn't keep the wait handles alive,
+    // so that they're freed once their listeners are dropped.
+    handles: Vec<(libc::c_int, Weak<WaitHandle>)>,
+}
+
+/// Creates a wait handle listener that is signaled
+/// once any of the provided Unix signals are received.
+///
+/// This replaces any existing handlers for the provided signals.
+/// The handlers stay installed for the rest of the process' lifetime,
+/// but the wait handle is unregistered once every listener is dropped.
+pub fn listen(signals: &[libc::c_int]) -> io::Result<WaitHandleListener> {
+    let registry = registry()?;
+    let mut registry = registry.lock().unwrap_or_else(PoisonError::into_inner);
+    registry.handles.retain(|(_, handle)| handle.strong_count() > 0);
+
+    let handle = Arc::new(WaitHandle::new(ResetMode::Manual));
+    for &signal in signals {
+        if !registry.installed.contains(&signal) {
+            install(signal)?;
+            registry.installed.insert(signal);
+        }
+        registry.handles.push((signal, Arc::downgrade(&handle)));
+    }
+
+    Ok(WaitHandleListener::new(handle))
+}
+
+fn registry() -> io::Result<&'static Mutex<Registry>> {
+    static INIT: Mutex<()> = Mutex::new(());
+
+    if let Some(registry) = REGISTRY.get() {
+        return Ok(registry);
+    }
+
+    // Creating the pipe can fail, so make sure
+    // that only one thread tries at a time.
+    let _guard = INIT.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(registry) = REGISTRY.get() {
+        return Ok(registry);
+    }
+
+    let (read, write) = pipe()?;
+    thread::Builder::new()
+        .name("waithandle-signals".into())
+        .spawn(move || dispatch(read))?;
+    WRITE_FD.store(write.as_raw_fd(), Ordering::SeqCst);
+    // The write end is used by the signal handler for the
+    // rest of the process' lifetime, so never close it.
+    mem::forget(write);
+
+    Ok(REGISTRY.get_or_init(Default::default))
+}
+
+fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    let read = unsafe { OwnedFd::from_raw_fd(fds[0]) };
+    let write = unsafe { OwnedFd::from_raw_fd(fds[1]) };
+
+    // Never block the signal handler, even if the pipe is full.
+    let flags = unsafe { libc::fcntl(write.as_raw_fd(), libc::F_GETFL) };
+    if flags == -1
+        || unsafe { libc::fcntl(write.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK) } == -1
+    {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok((read, write))
+}
+
+fn install(signal: libc::c_int) -> io::Result<()> {
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+        if libc::sigaction(signal, &action, std::ptr::null_mut()) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+extern "C" fn handler(signal: libc::c_int) {
+    // Only async-signal-safe functions can be called here.
+    unsafe {
+        let errno = *libc::__errno_location();
+        let byte = signal as u8;
+        libc::write(
+            WRITE_FD.load(Ordering::Relaxed),
+            &byte as *const u8 as *const libc::c_void,
+            1,
+        );
+        *libc::__errno_location() = errno;
+    }
+}
+
+fn dispatch(read: OwnedFd) {
+    loop {
+        let mut byte = 0u8;
+        let result = unsafe {
+            libc::read(
+                read.as_raw_fd(),
+                &mut byte as *mut u8 as *mut libc::c_void,
+                1,
+            )
+        };
+        match result {
+            1 => {}
+            -1 if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted => continue,
+            _ => return,
+        }
+
+        // Handlers are only installed once the registry exists.
+        let signal = libc::c_int::from(byte);
+        let Some(registry) = REGISTRY.get() else {
+            continue;
+        };
+        let mut registry = registry.lock().unwrap_or_else(PoisonError::into_inner);
+        registry.handles.retain(|(_, handle)| handle.strong_count() > 0);
+        let handles: Vec<_> = registry
+            .handles
+            .iter()
+            .filter(|(other, _)| *other == signal)
+            .filter_map(|(_, handle)| handle.upgrade())
+            .collect();
+        drop(registry);
+
+        // Signal the wait handles outside of the lock, since callbacks
+        // registered with them might call `listen`. A panicking callback
+        // mustn't take down the thread dispatching the signals.
+        for handle in handles {
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| handle.signal()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn registered(signal: libc::c_int) -> usize {
+        let registry = registry().unwrap().lock().unwrap();
+        registry.handles.iter().filter(|(other, _)| *other == signal).count()
+    }
+
+    #[test]
+    fn dropped_listeners_are_unregistered() {
+        let signal = libc::SIGRTMIN();
+        for _ in 0..10 {
+            drop(listen(&[signal]).unwrap());
+        }
+        let listener = listen(&[signal]).unwrap();
+        assert_eq!(1, registered(signal));
+
+        unsafe {
+            libc::raise(signal);
+        }
+        assert!(listener.wait(Duration::from_secs(5)));
+
+        // Dropped listeners are pruned the next time the registry is used.
+        drop(listener);
+        drop(listen(&[signal + 1]).unwrap());
+        assert_eq!(0, registered(signal));
+    }
+}
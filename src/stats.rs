@@ -0,0 +1,108 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::sync::atomic::Ordering;
+use std::sync::PoisonError;
+use std::time::Instant;
+
+use crate::WaitHandle;
+
+/// A snapshot of a wait handle's state and statistics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaitHandleStats {
+    name: Option<String>,
+    signaled: bool,
+    waiters: usize,
+    signals: usize,
+    resets: usize,
+    last_signaled: Option<Instant>,
+}
+
+impl WaitHandleStats {
+    pub(crate) fn new<T>(handle: &WaitHandle<T>) -> Self {
+        let last_signaled = handle
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .last_signaled;
+
+        Self {
+            name: handle.name.clone(),
+            signaled: handle.signaled.load(Ordering::Acquire),
+            waiters: handle.waiters.load(Ordering::Acquire),
+            signals: handle.signals.load(Ordering::Acquire),
+            resets: handle.resets.load(Ordering::Acquire),
+            last_signaled,
+        }
+    }
+
+    /// Gets the name of the wait handle, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Checks whether or not the wait handle was signaled.
+    pub fn is_signaled(&self) -> bool {
+        self.signaled
+    }
+
+    /// Gets the number of threads that were waiting for the wait handle.
+    pub fn waiters(&self) -> usize {
+        self.waiters
+    }
+
+    /// Gets the number of times the wait handle had been signaled.
+    ///
+    /// Every signal is counted, even if the wait handle already was signaled.
+    pub fn signals(&self) -> usize {
+        self.signals
+    }
+
+    /// Gets the number of times the wait handle had been reset.
+    ///
+    /// Every reset is counted, even if the wait handle wasn't signaled.
+    /// Signals consumed by auto-reset wait handles aren't counted.
+    pub fn resets(&self) -> usize {
+        self.resets
+    }
+
+    /// Gets the last time the wait handle was signaled, if ever.
+    pub fn last_signaled(&self) -> Option<Instant> {
+        self.last_signaled
+    }
+}
+
+impl fmt::Display for WaitHandleStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "wait handle '{}'", name)?,
+            None => write!(f, "wait handle")?,
+        }
+        write!(
+            f,
+            " ({}, {} waiters, {} signals, {} resets)",
+            if self.signaled { "signaled" } else { "unsignaled" },
+            self.waiters,
+            self.signals,
+            self.resets
+        )
+    }
+}
+
+/// Counts a thread as waiting for a wait handle,
+/// until the waiter is dropped.
+pub(crate) struct Waiter<'a, T> {
+    handle: &'a WaitHandle<T>,
+}
+
+impl<'a, T> Waiter<'a, T> {
+    pub(crate) fn new(handle: &'a WaitHandle<T>) -> Self {
+        handle.waiters.fetch_add(1, Ordering::AcqRel);
+        Self { handle }
+    }
+}
+
+impl<T> Drop for Waiter<'_, T> {
+    fn drop(&mut self) {
+        self.handle.waiters.fetch_sub(1, Ordering::AcqRel);
+    }
+}
@@ -0,0 +1,104 @@
+//! A minimal timer used to wake up tasks waiting for a wait handle
+//! with a timeout, without depending on a specific async runtime.
+
+use std::collections::BTreeMap;
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::task::Waker;
+use std::thread;
+use std::time::Instant;
+
+/// Identifies a waker registered with the timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct TimerKey {
+    deadline: Instant,
+    id: u64,
+}
+
+/// Wakes the provided waker once the deadline have passed,
+/// unless it's cancelled before that.
+pub(crate) fn register(deadline: Instant, waker: Waker) -> TimerKey {
+    let timer = timer();
+    let mut entries = timer.lock();
+    let key = TimerKey {
+        deadline,
+        id: entries.next_id,
+    };
+    entries.next_id = entries.next_id.wrapping_add(1);
+    entries.wakers.insert(key, waker);
+    timer.cvar.notify_one();
+    key
+}
+
+/// Removes a waker from the timer, so that it isn't kept
+/// alive until the deadline have passed.
+pub(crate) fn cancel(key: TimerKey) {
+    timer().lock().wakers.remove(&key);
+}
+
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    TIMER.get_or_init(|| {
+        thread::Builder::new()
+            .name("waithandle-timer".into())
+            .spawn(|| timer().run())
+            .expect("failed to spawn wait handle timer thread");
+        Timer {
+            entries: Mutex::new(Entries {
+                next_id: 0,
+                wakers: BTreeMap::new(),
+            }),
+            cvar: Condvar::new(),
+        }
+    })
+}
+
+struct Timer {
+    entries: Mutex<Entries>,
+    cvar: Condvar,
+}
+
+// The wakers are ordered by deadline, so that
+// the earliest deadline always comes first.
+struct Entries {
+    next_id: u64,
+    wakers: BTreeMap<TimerKey, Waker>,
+}
+
+impl Timer {
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn run(&self) {
+        let mut entries = self.lock();
+        loop {
+            let now = Instant::now();
+            let mut expired = Vec::new();
+            while let Some(entry) = entries.wakers.first_entry() {
+                if entry.key().deadline > now {
+                    break;
+                }
+                expired.push(entry.remove());
+            }
+
+            if !expired.is_empty() {
+                // Wake the tasks outside of the lock.
+                drop(entries);
+                expired.into_iter().for_each(Waker::wake);
+                entries = self.lock();
+                continue;
+            }
+
+            entries = match entries.wakers.keys().next() {
+                Some(key) => {
+                    let timeout = key.deadline - now;
+                    self.cvar
+                        .wait_timeout(entries, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self.cvar.wait(entries).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Instrumentation of waits, emitted with the `tracing` feature enabled.
+
+use std::time::Instant;
+
+use tracing::span::EnteredSpan;
+
+/// A span covering a wait for a wait handle,
+/// which records the outcome once finished.
+pub(crate) struct WaitSpan {
+    _span: EnteredSpan,
+    start: Instant,
+}
+
+impl WaitSpan {
+    pub fn enter(name: Option<&str>) -> Self {
+        let span = tracing::debug_span!("wait", name).entered();
+        tracing::trace!("waiting for wait handle");
+        Self {
+            _span: span,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn finish(self, outcome: &str) {
+        tracing::debug!(
+            elapsed = ?self.start.elapsed(),
+            outcome,
+            "waited for wait handle"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "async")]
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn signal_wakes_async_and_blocking_listeners() {
+    let (signaler, listener) = waithandle::new();
+
+    let blocking = thread::spawn({
+        let listener = listener.clone();
+        move || listener.wait(Duration::from_secs(30))
+    });
+    let task = thread::spawn({
+        let listener = listener.clone();
+        move || block_on(listener.wait_async(None))
+    });
+
+    thread::sleep(Duration::from_millis(100));
+    signaler.signal();
+
+    assert!(blocking.join().unwrap());
+    assert!(task.join().unwrap());
+}
+
+#[test]
+fn async_wait_times_out() {
+    let (_signaler, listener) = waithandle::new();
+
+    let start = Instant::now();
+    assert!(!block_on(listener.wait_async(Some(Duration::from_millis(50)))));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn async_wait_times_out_once_disconnected() {
+    let (signaler, listener) = waithandle::new();
+    drop(signaler);
+
+    let start = Instant::now();
+    assert!(!block_on(listener.wait_async(Some(Duration::from_millis(50)))));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(block_on(listener.try_wait_async(None)).is_err());
+}
+
+#[test]
+fn dropped_future_releases_its_waker() {
+    let (_signaler, listener) = waithandle::new();
+    let waker = Arc::new(ThreadWaker(thread::current()));
+
+    let mut future = Box::pin(listener.wait_async(Some(Duration::from_secs(3600))));
+    let task_waker = waker.clone().into();
+    let mut cx = Context::from_waker(&task_waker);
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    drop(task_waker);
+    assert!(Arc::strong_count(&waker) > 1);
+
+    // Neither the wait handle nor the timer keeps the waker alive.
+    drop(future);
+    assert_eq!(Arc::strong_count(&waker), 1);
+}
@@ -0,0 +1,51 @@
+#![cfg(all(feature = "eventfd", target_os = "linux"))]
+
+use std::os::unix::io::AsRawFd;
+
+fn is_readable(fd: i32) -> bool {
+    let mut pollfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut pollfd, 1, 0) == 1 }
+}
+
+#[test]
+fn eventfd_is_readable_while_signaled() {
+    let (signaler, listener) = waithandle::new();
+    let fd = listener.as_raw_fd();
+    assert!(!is_readable(fd));
+
+    signaler.signal();
+    assert!(is_readable(fd));
+    assert!(listener.check());
+    assert!(is_readable(fd));
+
+    signaler.reset();
+    assert!(!is_readable(fd));
+}
+
+#[test]
+fn eventfd_of_auto_reset_handle_is_drained_by_check() {
+    let (signaler, listener) = waithandle::new_auto_reset();
+    signaler.signal();
+
+    // Created after the signal, but still readable.
+    let fd = listener.as_raw_fd();
+    assert!(is_readable(fd));
+
+    assert!(listener.check());
+    assert!(!is_readable(fd));
+}
+
+#[test]
+fn try_eventfd_returns_the_same_eventfd() {
+    let (signaler, listener) = waithandle::new();
+    let fd = listener.try_eventfd().unwrap().as_raw_fd();
+    assert_eq!(fd, listener.as_raw_fd());
+    assert_eq!(fd, listener.clone().try_eventfd().unwrap().as_raw_fd());
+
+    signaler.signal();
+    assert!(is_readable(fd));
+}
@@ -0,0 +1,85 @@
+#![cfg(all(feature = "futex", target_os = "linux"))]
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn signal_wakes_every_blocked_listener() {
+    let (signaler, listener) = waithandle::new();
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let listener = listener.clone();
+            thread::spawn(move || listener.wait(Duration::from_secs(10)))
+        })
+        .collect();
+
+    while signaler.stats().waiters() < 4 {
+        thread::yield_now();
+    }
+    signaler.signal();
+
+    for thread in threads {
+        assert!(thread.join().unwrap());
+    }
+}
+
+#[test]
+fn signal_wakes_one_blocked_listener_of_auto_reset_handle() {
+    let (signaler, listener) = waithandle::new_auto_reset();
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let listener = listener.clone();
+            thread::spawn(move || listener.wait(Duration::from_millis(500)))
+        })
+        .collect();
+
+    while signaler.stats().waiters() < 2 {
+        thread::yield_now();
+    }
+    signaler.signal();
+
+    let woken = threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .filter(|signaled| *signaled)
+        .count();
+    assert_eq!(1, woken);
+}
+
+#[test]
+fn wait_times_out_without_signal() {
+    let (_signaler, listener) = waithandle::new();
+    let start = Instant::now();
+
+    assert!(!listener.wait(Duration::from_millis(100)));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn ping_pong_between_threads() {
+    let (ping, ping_listener) = waithandle::new_auto_reset();
+    let (pong, pong_listener) = waithandle::new_auto_reset();
+    let thread = thread::spawn(move || {
+        for _ in 0..1000 {
+            ping_listener.wait_forever();
+            pong.signal();
+        }
+    });
+
+    for _ in 0..1000 {
+        ping.signal();
+        pong_listener.wait_forever();
+    }
+    thread.join().unwrap();
+}
+
+#[test]
+fn released_permit_wakes_blocked_acquirer() {
+    let (signaler, listener) = waithandle::semaphore(0);
+    let thread = thread::spawn(move || listener.acquire(Duration::from_secs(10)));
+
+    thread::sleep(Duration::from_millis(50));
+    signaler.release();
+
+    assert!(thread.join().unwrap());
+}
@@ -0,0 +1,55 @@
+#![cfg(all(feature = "shm", target_os = "linux"))]
+
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn named_wait_handle_is_shared_between_mappings() {
+    let name = format!("/waithandle-test-{}", std::process::id());
+    let (signaler, _) = waithandle::shm::create(&name).unwrap();
+    let (_, listener) = waithandle::shm::open(&name).unwrap();
+    waithandle::shm::unlink(&name).unwrap();
+
+    assert!(!listener.wait(Duration::from_millis(10)));
+
+    let thread = thread::spawn(move || listener.wait(Duration::from_secs(30)));
+    thread::sleep(Duration::from_millis(50));
+    signaler.signal();
+    assert!(thread.join().unwrap());
+}
+
+#[test]
+fn anonymous_wait_handle_can_be_opened_from_fd() {
+    use std::os::unix::io::AsFd;
+
+    let (signaler, _) = waithandle::shm::anonymous().unwrap();
+    let fd = signaler.as_fd().try_clone_to_owned().unwrap();
+    let (_, listener) = waithandle::shm::from_fd(fd).unwrap();
+
+    signaler.signal();
+    assert!(listener.check());
+    signaler.reset();
+    assert!(!listener.check());
+}
+
+#[test]
+fn wait_handle_is_shared_with_forked_process() {
+    let (signaler, listener) = waithandle::shm::anonymous().unwrap();
+
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0, "fork failed");
+    if pid == 0 {
+        // Only async-signal-safe work in the child, since the
+        // test harness might have other threads running.
+        let signaled = listener.wait(Duration::from_secs(30));
+        unsafe { libc::_exit(if signaled { 0 } else { 1 }) };
+    }
+
+    thread::sleep(Duration::from_millis(100));
+    signaler.signal();
+
+    let mut status = 0;
+    assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+    assert!(libc::WIFEXITED(status));
+    assert_eq!(libc::WEXITSTATUS(status), 0);
+}
@@ -0,0 +1,18 @@
+#![cfg(all(feature = "signals", target_os = "linux"))]
+
+use std::time::Duration;
+
+#[test]
+fn raised_signal_signals_listener() {
+    let listener = waithandle::signals::listen(&[libc::SIGUSR1]).unwrap();
+    let other = waithandle::signals::listen(&[libc::SIGUSR2]).unwrap();
+    assert!(!listener.check());
+
+    unsafe {
+        libc::raise(libc::SIGUSR1);
+    }
+
+    assert!(listener.wait(Duration::from_secs(5)));
+    assert!(!other.wait(Duration::from_millis(50)));
+}
+
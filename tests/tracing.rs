@@ -0,0 +1,165 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::{self, Write};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use waithandle::{ResetMode, WaitHandleBuilder};
+
+type Probe = Arc<dyn Fn() + Send + Sync>;
+
+// Records spans and events as lines of text.
+#[derive(Clone, Default)]
+struct Recorder {
+    lines: Arc<Mutex<Vec<String>>>,
+    // Runs whenever an event is recorded.
+    probe: Option<Probe>,
+}
+
+impl Recorder {
+    fn lines(&self) -> Vec<String> {
+        self.lines.lock().unwrap().clone()
+    }
+
+    fn contains(&self, parts: &[&str]) -> bool {
+        self.lines()
+            .iter()
+            .any(|line| parts.iter().all(|part| line.contains(part)))
+    }
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        write!(self.0, " {}={:?}", field.name(), value).unwrap();
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(format!("span {}", span.metadata().name()));
+        span.record(&mut fields);
+        self.lines.lock().unwrap().push(fields.0);
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(format!("event {}", event.metadata().level()));
+        event.record(&mut fields);
+        self.lines.lock().unwrap().push(fields.0);
+
+        if let Some(probe) = &self.probe {
+            probe();
+        }
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn emits_events_for_signal_reset_and_wait() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let (signaler, listener) = WaitHandleBuilder::new().name("shutdown").build();
+
+        assert!(!listener.wait(Duration::from_millis(20)));
+        signaler.signal();
+        signaler.reset();
+    });
+
+    assert!(recorder.contains(&["span wait", "name=\"shutdown\""]));
+    assert!(recorder.contains(&["waiting for wait handle"]));
+    assert!(recorder.contains(&["waited for wait handle", "elapsed=", "outcome=\"timed out\""]));
+    assert!(recorder.contains(&["span signal", "name=\"shutdown\""]));
+    assert!(recorder.contains(&["wait handle signaled", "name=\"shutdown\""]));
+    assert!(recorder.contains(&["span reset", "name=\"shutdown\""]));
+    assert!(recorder.contains(&["wait handle reset", "name=\"shutdown\""]));
+}
+
+#[test]
+fn emits_events_for_wait_on_signaled_handle() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let (signaler, listener) = WaitHandleBuilder::new().name("shutdown").build();
+        signaler.signal();
+        assert!(listener.wait(Duration::from_secs(30)));
+    });
+
+    assert!(recorder.contains(&["span signal", "name=\"shutdown\""]));
+    assert!(recorder.contains(&["span wait", "name=\"shutdown\""]));
+    assert!(recorder.contains(&["waited for wait handle", "outcome=\"already signaled\""]));
+}
+
+#[test]
+fn emits_events_without_holding_the_lock() {
+    let (signaler, listener) = WaitHandleBuilder::new().reset_mode(ResetMode::Auto).build();
+
+    // Cloning a signaler takes the wait handle's lock,
+    // so the probe deadlocks if an event is emitted while holding it.
+    let recorder = Recorder {
+        probe: Some(Arc::new({
+            let signaler = signaler.clone();
+            move || drop(signaler.clone())
+        })),
+        ..Recorder::default()
+    };
+
+    let (done, finished) = mpsc::channel();
+    thread::spawn({
+        let recorder = recorder.clone();
+        move || {
+            tracing::subscriber::with_default(recorder, || {
+                signaler.signal();
+                assert!(listener.wait(Duration::from_secs(30)));
+                signaler.reset();
+            });
+            done.send(()).unwrap();
+        }
+    });
+
+    finished
+        .recv_timeout(Duration::from_secs(10))
+        .expect("an event was emitted while holding the lock");
+    assert!(recorder.contains(&["wait handle signaled"]));
+    assert!(recorder.contains(&["waited for wait handle", "outcome=\"already signaled\""]));
+    assert!(recorder.contains(&["wait handle reset"]));
+}
+
+#[test]
+fn emits_event_when_lock_is_poisoned() {
+    #[derive(Debug)]
+    struct Fragile;
+
+    impl Clone for Fragile {
+        fn clone(&self) -> Self {
+            panic!("cloning the payload failed");
+        }
+    }
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let (signaler, listener) = waithandle::new_with_payload::<Fragile>();
+        signaler.signal_with(Fragile);
+
+        let checking = std::panic::AssertUnwindSafe(|| listener.check());
+        assert!(std::panic::catch_unwind(checking).is_err());
+        assert!(listener.try_check().is_err());
+    });
+
+    assert!(recorder.contains(&["event WARN", "wait handle lock poisoned", "operation=check"]));
+}
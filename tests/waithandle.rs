@@ -0,0 +1,666 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn signal_wakes_all_cloned_listeners() {
+    const LISTENERS: usize = 8;
+    let (signaler, listener) = waithandle::new();
+
+    let threads: Vec<_> = (0..LISTENERS)
+        .map(|_| {
+            let listener = listener.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                let signaled = listener.wait(Duration::from_secs(30));
+                (signaled, start.elapsed())
+            })
+        })
+        .collect();
+
+    // Give the threads some time to start waiting.
+    thread::sleep(Duration::from_millis(100));
+    signaler.signal();
+
+    for thread in threads {
+        let (signaled, elapsed) = thread.join().unwrap();
+        assert!(signaled);
+        assert!(elapsed < Duration::from_secs(10));
+    }
+}
+
+#[test]
+fn auto_reset_signal_releases_one_waiter() {
+    let (signaler, listener) = waithandle::new_auto_reset();
+
+    signaler.signal();
+    assert!(listener.wait(Duration::from_millis(10)));
+    assert!(!listener.wait(Duration::from_millis(10)));
+
+    signaler.signal();
+    assert!(listener.check());
+    assert!(!listener.check());
+}
+
+#[test]
+fn wait_until_holds_deadline_across_retries() {
+    let (signaler, listener) = waithandle::new();
+
+    // Pulse the wait handle to cause partial wakeups.
+    let pulser = thread::spawn({
+        let signaler = signaler.clone();
+        move || {
+            for _ in 0..10 {
+                thread::sleep(Duration::from_millis(20));
+                signaler.signal();
+                signaler.reset();
+            }
+        }
+    });
+
+    let start = Instant::now();
+    let deadline = start + Duration::from_millis(300);
+    while listener.wait_until(deadline) {
+        // Woken up by a pulse, so wait again for the same deadline.
+    }
+
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(300));
+    assert!(elapsed < Duration::from_secs(5));
+
+    // Once the deadline have passed, waiting returns right away.
+    let start = Instant::now();
+    for _ in 0..5 {
+        assert!(!listener.wait_until(deadline));
+    }
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    pulser.join().unwrap();
+    drop(signaler);
+}
+
+#[test]
+fn wait_outcome_distinguishes_signal_from_timeout() {
+    use waithandle::WaitOutcome;
+
+    let (signaler, listener) = waithandle::new();
+
+    let status = listener.wait_outcome(Duration::from_millis(20));
+    assert_eq!(status.outcome(), WaitOutcome::TimedOut);
+    assert!(status.elapsed() >= Duration::from_millis(20));
+    assert_eq!(
+        status.into_result().unwrap_err().kind(),
+        waithandle::ErrorKind::TimedOut
+    );
+
+    let thread = thread::spawn({
+        let listener = listener.clone();
+        move || listener.wait_outcome(Duration::from_secs(30))
+    });
+    thread::sleep(Duration::from_millis(50));
+    signaler.signal();
+    assert_eq!(thread.join().unwrap().outcome(), WaitOutcome::Signaled);
+
+    let status = listener.wait_outcome(Duration::from_secs(30));
+    assert_eq!(status.outcome(), WaitOutcome::AlreadySignaled);
+}
+
+#[test]
+fn dropping_all_signalers_disconnects_listeners() {
+    use waithandle::{ErrorKind, Operation, WaitOutcome};
+
+    let (signaler, listener) = waithandle::new();
+    let other = signaler.clone();
+
+    let thread = thread::spawn({
+        let listener = listener.clone();
+        move || listener.try_wait(Duration::from_secs(30))
+    });
+
+    drop(signaler);
+    assert!(!listener.check());
+
+    thread::sleep(Duration::from_millis(50));
+    drop(other);
+
+    let err = thread.join().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Disconnected);
+    assert_eq!(err.operation(), Operation::Wait);
+    assert_eq!(err.to_string(), "failed to wait for wait handle: disconnected");
+    assert_eq!(
+        listener.wait_outcome(Duration::from_secs(30)).outcome(),
+        WaitOutcome::Disconnected
+    );
+}
+
+#[test]
+fn disconnected_listeners_report_not_signaled() {
+    let timeout = Duration::from_millis(50);
+    let (signaler, listener) = waithandle::new();
+    drop(signaler);
+
+    assert!(!listener.check());
+
+    // The timed waits keep waiting until the timeout occur,
+    // so that waiting in a loop doesn't spin.
+    let start = Instant::now();
+    assert!(!listener.wait(timeout));
+    assert!(start.elapsed() >= timeout);
+
+    let start = Instant::now();
+    assert!(!listener.wait_until(start + timeout));
+    assert!(start.elapsed() >= timeout);
+
+    let start = Instant::now();
+    assert_eq!(waithandle::wait_any(&[&listener], timeout), None);
+    assert!(start.elapsed() >= timeout);
+
+    let start = Instant::now();
+    assert!(!waithandle::wait_all(&[&listener], timeout));
+    assert!(start.elapsed() >= timeout);
+
+    let (signaler, listener) = waithandle::new_with_payload::<u32>();
+    drop(signaler);
+    assert_eq!(listener.check(), None);
+
+    let start = Instant::now();
+    assert_eq!(listener.wait(timeout), None);
+    assert!(start.elapsed() >= timeout);
+
+    let (signaler, listener) = waithandle::semaphore(0);
+    drop(signaler);
+
+    let start = Instant::now();
+    assert!(!listener.acquire(timeout));
+    assert!(start.elapsed() >= timeout);
+}
+
+#[test]
+fn wait_any_returns_index_of_signaled_handle() {
+    let (_shutdown, shutdown_listener) = waithandle::new();
+    let (reload, reload_listener) = waithandle::new();
+    let listeners = [&shutdown_listener, &reload_listener];
+
+    assert_eq!(waithandle::wait_any(&listeners, Duration::from_millis(20)), None);
+
+    let thread = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        reload.signal();
+        reload
+    });
+    assert_eq!(waithandle::wait_any(&listeners, Duration::from_secs(30)), Some(1));
+    thread.join().unwrap();
+}
+
+#[test]
+fn wait_all_waits_for_every_handle() {
+    let (first, first_listener) = waithandle::new_auto_reset();
+    let (second, second_listener) = waithandle::new_auto_reset();
+    let listeners = [&first_listener, &second_listener];
+
+    first.signal();
+    assert!(!waithandle::wait_all(&listeners, Duration::from_millis(20)));
+
+    let thread = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        second.signal();
+        second
+    });
+    assert!(waithandle::wait_all(&listeners, Duration::from_secs(30)));
+    let _second = thread.join().unwrap();
+
+    // Both signals were consumed by the wait.
+    assert!(!first_listener.check());
+    assert!(!second_listener.check());
+}
+
+#[test]
+fn wait_all_succeeds_when_signaled_handle_is_disconnected() {
+    let (first, first_listener) = waithandle::new();
+    let (second, second_listener) = waithandle::new();
+    let listeners = [&first_listener, &second_listener];
+
+    first.signal();
+    drop(first);
+
+    let thread = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        second.signal();
+        second
+    });
+    assert!(waithandle::try_wait_all(&listeners, Duration::from_secs(2)).unwrap());
+    let _second = thread.join().unwrap();
+}
+
+#[test]
+fn payload_listener_receives_signaled_value() {
+    #[derive(Debug, Clone, PartialEq)]
+    enum Reason {
+        Graceful,
+        Abort,
+    }
+
+    let (signaler, listener) = waithandle::new_with_payload::<Reason>();
+    assert_eq!(listener.wait(Duration::from_millis(10)), None);
+
+    let thread = thread::spawn({
+        let listener = listener.clone();
+        move || listener.wait(Duration::from_secs(30))
+    });
+    thread::sleep(Duration::from_millis(50));
+    signaler.signal_with(Reason::Graceful);
+    assert_eq!(thread.join().unwrap(), Some(Reason::Graceful));
+
+    signaler.signal_with(Reason::Abort);
+    assert_eq!(listener.check(), Some(Reason::Abort));
+
+    signaler.reset();
+    assert_eq!(listener.check(), None);
+}
+
+#[test]
+fn semaphore_limits_permits() {
+    let (signaler, listener) = waithandle::semaphore(2);
+
+    assert!(listener.acquire(Duration::from_millis(10)));
+    assert!(listener.acquire(Duration::from_millis(10)));
+    assert!(!listener.acquire(Duration::from_millis(10)));
+
+    let thread = thread::spawn({
+        let listener = listener.clone();
+        move || listener.acquire(Duration::from_secs(30))
+    });
+    thread::sleep(Duration::from_millis(50));
+    signaler.release();
+    assert!(thread.join().unwrap());
+    assert_eq!(listener.available(), 0);
+}
+
+#[test]
+fn barrier_releases_times_out_and_cancels() {
+    use waithandle::BarrierOutcome;
+
+    let (canceller, barrier) = waithandle::barrier(3);
+
+    // Only two of three participants arrive.
+    assert_eq!(barrier.wait(Duration::from_millis(20)), BarrierOutcome::TimedOut);
+
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let barrier = barrier.clone();
+            thread::spawn(move || barrier.wait(Duration::from_secs(30)))
+        })
+        .collect();
+    let outcome = barrier.wait(Duration::from_secs(30));
+    let outcomes: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    assert!(outcome.is_released());
+    assert!(outcomes.iter().all(BarrierOutcome::is_released));
+    assert_eq!(
+        outcomes.iter().chain([outcome].iter()).filter(|o| o.is_leader()).count(),
+        1
+    );
+
+    let thread = thread::spawn({
+        let barrier = barrier.clone();
+        move || barrier.wait(Duration::from_secs(30))
+    });
+    thread::sleep(Duration::from_millis(50));
+    canceller.cancel();
+    assert_eq!(thread.join().unwrap(), BarrierOutcome::Cancelled);
+    assert_eq!(barrier.wait(Duration::from_secs(30)), BarrierOutcome::Cancelled);
+}
+
+#[test]
+fn cancellation_propagates_from_parent_to_children() {
+    use waithandle::CancellationToken;
+
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+    let grandchild = child.child_token();
+    let sibling = parent.child_token();
+
+    sibling.cancel();
+    assert!(!parent.is_cancelled());
+    assert!(!child.is_cancelled());
+
+    // Dropping a child doesn't affect the parent.
+    drop(sibling);
+
+    let thread = thread::spawn({
+        let grandchild = grandchild.clone();
+        move || grandchild.wait(Duration::from_secs(30))
+    });
+    thread::sleep(Duration::from_millis(50));
+    parent.cancel();
+
+    assert!(thread.join().unwrap());
+    assert!(child.is_cancelled());
+    assert!(parent.child_token().is_cancelled());
+}
+
+#[test]
+fn dropping_parent_does_not_cancel_children() {
+    let parent = waithandle::CancellationToken::new();
+    let child = parent.child_token();
+
+    drop(parent);
+    assert!(!child.wait(Duration::from_millis(20)));
+}
+
+#[test]
+fn on_signal_runs_callback_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let (signaler, listener) = waithandle::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let counter = calls.clone();
+    let _guard = listener.on_signal(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    let counter = calls.clone();
+    let unregistered = listener.on_signal(move || {
+        counter.fetch_add(100, Ordering::SeqCst);
+    });
+    drop(unregistered);
+
+    signaler.signal();
+    signaler.reset();
+    signaler.signal();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Already signaled, so the callback runs right away.
+    let counter = calls.clone();
+    let _guard = listener.on_signal(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+// A payload that panics the first time it's cloned,
+// which poisons the lock of the wait handle carrying it.
+#[derive(Debug)]
+struct Fragile(std::sync::Arc<std::sync::atomic::AtomicBool>);
+
+impl Fragile {
+    fn new() -> Self {
+        Fragile(std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true)))
+    }
+}
+
+impl Clone for Fragile {
+    fn clone(&self) -> Self {
+        if self.0.swap(false, std::sync::atomic::Ordering::SeqCst) {
+            panic!("cloning the payload failed");
+        }
+        Fragile(self.0.clone())
+    }
+}
+
+fn poison(
+    signaler: &waithandle::PayloadSignaler<Fragile>,
+    listener: &waithandle::PayloadListener<Fragile>,
+) {
+    signaler.signal_with(Fragile::new());
+    let listener = listener.clone();
+    assert!(thread::spawn(move || listener.check()).join().is_err());
+}
+
+#[test]
+fn poisoned_lock_fails_by_default() {
+    use std::error::Error;
+    use waithandle::{ErrorKind, Operation};
+
+    let (signaler, listener) = waithandle::new_with_payload::<Fragile>();
+    poison(&signaler, &listener);
+
+    let err = listener.try_check().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Poisoned);
+    assert_eq!(err.operation(), Operation::Check);
+    assert!(err.source().is_some());
+
+    let err = signaler.try_reset().unwrap_err();
+    assert_eq!(err.operation(), Operation::Reset);
+}
+
+#[test]
+fn poisoned_lock_is_recovered_according_to_policy() {
+    use waithandle::PoisonPolicy;
+
+    for policy in [PoisonPolicy::Recover, PoisonPolicy::Clear] {
+        let (signaler, listener) =
+            waithandle::new_with_payload_and_poison_policy::<Fragile>(policy);
+        poison(&signaler, &listener);
+
+        assert!(listener.check().is_some());
+        assert!(listener.wait(Duration::from_secs(30)).is_some());
+
+        signaler.reset();
+        assert!(listener.wait(Duration::from_millis(10)).is_none());
+
+        let thread = thread::spawn({
+            let listener = listener.clone();
+            move || listener.wait(Duration::from_secs(30))
+        });
+        thread::sleep(Duration::from_millis(50));
+        signaler.signal_with(Fragile::new());
+        // The new payload panics when cloned, so the waiting thread
+        // panics as well, but the wait handle keeps working.
+        assert!(thread.join().is_err());
+        assert!(listener.check().is_some());
+    }
+}
+
+#[test]
+fn builder_configures_wait_handle() {
+    use waithandle::{ResetMode, WaitHandleBuilder, WakeMode};
+
+    let (_signaler, listener) = WaitHandleBuilder::new()
+        .name("shutdown")
+        .signaled(true)
+        .build();
+    assert!(listener.check());
+    assert!(listener.check());
+
+    // Starting out signaled doesn't count as a signal.
+    let stats = listener.stats();
+    assert!(stats.is_signaled());
+    assert_eq!(stats.signals(), 0);
+    assert_eq!(stats.last_signaled(), None);
+
+    let (_signaler, listener) = WaitHandleBuilder::new()
+        .reset_mode(ResetMode::Auto)
+        .signaled(true)
+        .build();
+    assert!(listener.check());
+    assert!(!listener.check());
+
+    // Only one of the waiting threads is woken up by the signal,
+    // while the other one only notices it once its timeout occur.
+    let (signaler, listener) = WaitHandleBuilder::new().wake_mode(WakeMode::One).build();
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let listener = listener.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                assert!(listener.wait(Duration::from_secs(1)));
+                start.elapsed()
+            })
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(50));
+    signaler.signal();
+
+    let mut elapsed: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    elapsed.sort();
+    assert!(elapsed[0] < Duration::from_millis(500));
+    assert!(elapsed[1] >= Duration::from_secs(1));
+}
+
+#[test]
+fn stats_describe_wait_handle() {
+    use waithandle::WaitHandleBuilder;
+
+    let (signaler, listener) = WaitHandleBuilder::new().name("shutdown").build();
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let listener = listener.clone();
+            thread::spawn(move || listener.wait(Duration::from_secs(30)))
+        })
+        .collect();
+    while listener.stats().waiters() < 2 {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let stats = listener.stats();
+    assert_eq!(stats.name(), Some("shutdown"));
+    assert!(!stats.is_signaled());
+    assert_eq!(
+        listener.to_string(),
+        "wait handle 'shutdown' (unsignaled, 2 waiters, 0 signals, 0 resets)"
+    );
+
+    signaler.signal();
+    for thread in threads {
+        assert!(thread.join().unwrap());
+    }
+    assert_eq!(
+        signaler.to_string(),
+        "wait handle 'shutdown' (signaled, 0 waiters, 1 signals, 0 resets)"
+    );
+    assert!(format!("{:?}", listener).contains("name: Some(\"shutdown\")"));
+}
+
+#[test]
+fn stats_count_signals_and_resets() {
+    let (signaler, listener) = waithandle::new();
+    assert_eq!(signaler.stats().last_signaled(), None);
+
+    let before = Instant::now();
+    signaler.signal();
+    signaler.signal();
+    signaler.reset();
+    signaler.reset();
+    signaler.signal();
+
+    let stats = signaler.stats();
+    assert_eq!(stats.signals(), 3);
+    assert_eq!(stats.resets(), 2);
+    assert!(stats.last_signaled().unwrap() >= before);
+    assert_eq!(stats, listener.stats());
+}
+
+#[test]
+fn panicking_callback_does_not_keep_other_callbacks_from_running() {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let (signaler, listener) = waithandle::new();
+    let ran = Arc::new(AtomicBool::new(false));
+
+    let _panicking = listener.on_signal(|| panic!("callback failed"));
+    let _guard = listener.on_signal({
+        let ran = ran.clone();
+        move || ran.store(true, Ordering::SeqCst)
+    });
+
+    // The panic is resumed once every callback have been run.
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| signaler.signal())).is_err());
+    assert!(ran.load(Ordering::SeqCst));
+    assert!(listener.check());
+}
+
+#[test]
+fn countdown_event_wakes_waiters_once_count_reaches_zero() {
+    use waithandle::CountdownEvent;
+
+    let countdown = CountdownEvent::new(3);
+    assert_eq!(countdown.remaining(), 3);
+
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let countdown = countdown.clone();
+            thread::spawn(move || countdown.wait(Duration::from_secs(30)))
+        })
+        .collect();
+
+    countdown.signal();
+    countdown.signal();
+    assert_eq!(countdown.remaining(), 1);
+    assert!(!countdown.wait(Duration::from_millis(20)));
+
+    countdown.signal();
+    assert_eq!(countdown.remaining(), 0);
+    for thread in threads {
+        assert!(thread.join().unwrap());
+    }
+    assert!(countdown.check());
+}
+
+#[test]
+fn countdown_event_ignores_extra_signals() {
+    use waithandle::CountdownEvent;
+
+    let countdown = CountdownEvent::new(1);
+    countdown.signal();
+    countdown.signal();
+    assert_eq!(countdown.remaining(), 0);
+    assert!(countdown.check());
+}
+
+#[test]
+fn countdown_event_without_count_starts_out_signaled() {
+    use waithandle::CountdownEvent;
+
+    let countdown = CountdownEvent::new(0);
+    assert_eq!(countdown.remaining(), 0);
+    assert!(countdown.check());
+    assert!(countdown.wait(Duration::from_millis(10)));
+}
+
+#[test]
+fn wait_forever_returns_once_signaled() {
+    let (signaler, listener) = waithandle::new();
+
+    let thread = thread::spawn({
+        let listener = listener.clone();
+        move || listener.wait_forever()
+    });
+    thread::sleep(Duration::from_millis(50));
+    assert!(!thread.is_finished());
+
+    signaler.signal();
+    thread.join().unwrap();
+}
+
+#[test]
+fn try_wait_forever_fails_once_disconnected() {
+    use waithandle::ErrorKind;
+
+    let (signaler, listener) = waithandle::new();
+
+    let thread = thread::spawn({
+        let listener = listener.clone();
+        move || listener.try_wait_forever()
+    });
+    thread::sleep(Duration::from_millis(50));
+    drop(signaler);
+
+    let err = thread.join().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Disconnected);
+}
+
+#[test]
+fn wait_forever_keeps_waiting_once_disconnected() {
+    let (signaler, listener) = waithandle::new();
+
+    let thread = thread::spawn(move || listener.wait_forever());
+    thread::sleep(Duration::from_millis(50));
+    drop(signaler);
+
+    // Nothing can signal the wait handle anymore, so the thread
+    // never finishes and is left behind once the test is done.
+    thread::sleep(Duration::from_millis(50));
+    assert!(!thread.is_finished());
+}